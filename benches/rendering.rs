@@ -0,0 +1,71 @@
+//! Host benchmarks of the frame write paths, run with `cargo bench`.
+//!
+//! The driver talks to a [`NullInterface`], so only the work done by the driver
+//! itself (buffer conversion, chunking, coalescing) is measured.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lp586x::{interface::RegisterAccess, Error, Lp5860, PwmAccess};
+
+/// Interface discarding all writes, counting the transferred bytes.
+#[derive(Default)]
+struct NullInterface {
+    bytes_written: usize,
+}
+
+impl RegisterAccess for NullInterface {
+    type Error = Error<()>;
+
+    fn read_registers(&mut self, _start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        data.fill(0);
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        self.bytes_written += data.len();
+        Ok(())
+    }
+}
+
+fn full_frame(c: &mut Criterion) {
+    let mut driver_8bit = Lp5860::new(NullInterface::default())
+        .unwrap()
+        .into_8bit_data_mode()
+        .unwrap();
+    let frame_8bit = [0x55u8; <Lp5860>::NUM_DOTS];
+    c.bench_function("full frame 8 bit", |b| {
+        b.iter(|| driver_8bit.set_pwm(0, black_box(&frame_8bit)).unwrap())
+    });
+
+    let mut driver_16bit = Lp5860::new(NullInterface::default())
+        .unwrap()
+        .into_16bit_data_mode()
+        .unwrap();
+    let frame_16bit = [0x5555u16; <Lp5860>::NUM_DOTS];
+    c.bench_function("full frame 16 bit", |b| {
+        b.iter(|| driver_16bit.set_pwm(0, black_box(&frame_16bit)).unwrap())
+    });
+}
+
+fn sparse_pixels(c: &mut Criterion) {
+    let mut driver = Lp5860::new(NullInterface::default())
+        .unwrap()
+        .into_8bit_data_mode()
+        .unwrap();
+
+    // every third dot, in reverse order, so sorting and run coalescing are exercised
+    let pixels: Vec<(u16, u8)> = (0..<Lp5860>::NUM_DOTS as u16)
+        .rev()
+        .filter(|dot| dot % 3 != 0)
+        .map(|dot| (dot, dot as u8))
+        .collect();
+
+    c.bench_function("sparse pixels", |b| {
+        b.iter(|| {
+            let mut pixels = pixels.clone();
+            driver.set_pixels(black_box(&mut pixels)).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, full_frame, sparse_pixels);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+#![no_main]
+
+//! Packing of dot group assignments of any length into the dot group registers.
+
+use libfuzzer_sys::fuzz_target;
+use lp586x::{loopback::LoopbackInterface, DotGroup, Lp5860, Lp5864};
+
+fn dot_group(byte: u8) -> DotGroup {
+    match byte % 4 {
+        0 => DotGroup::None,
+        1 => DotGroup::Group0,
+        2 => DotGroup::Group1,
+        _ => DotGroup::Group2,
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let dot_groups: Vec<DotGroup> = data.iter().copied().map(dot_group).collect();
+
+    let mut lp5860 = Lp5860::new(LoopbackInterface::new()).unwrap();
+    let _ = lp5860.set_dot_groups(&dot_groups);
+
+    let mut lp5864 = Lp5864::new(LoopbackInterface::new()).unwrap();
+    let _ = lp5864.set_dot_groups(&dot_groups);
+
+    if let Some((&first, rest)) = data.split_first() {
+        let dot = u16::from_le_bytes([first, rest.first().copied().unwrap_or(0)]);
+        let _ = lp5864.set_dot_group(dot, dot_group(first));
+    }
+});
@@ -0,0 +1,29 @@
+#![no_main]
+
+//! Decoding of arbitrary fault register contents, into buffers of any length.
+
+use libfuzzer_sys::fuzz_target;
+use lp586x::{interface::RegisterAccess, loopback::LoopbackInterface, GlobalFaultState, Lp5862};
+
+/// First fault register, FAULT_STATE, followed by the LOD and LSD registers
+const FAULT_REGISTERS_START: u16 = 0x064;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&len, registers)) = data.split_first() else {
+        return;
+    };
+    let _ = GlobalFaultState::from_reg_value(len);
+
+    let mut interface = LoopbackInterface::new();
+    let registers = &registers[..registers.len().min(1 + 2 * 33)];
+    interface
+        .write_registers(FAULT_REGISTERS_START, registers)
+        .unwrap();
+
+    let mut ledmatrix = Lp5862::new(interface).unwrap();
+    let mut dots = vec![false; len as usize];
+    ledmatrix.get_led_open_states(&mut dots).unwrap();
+    ledmatrix.get_led_short_states(&mut dots).unwrap();
+    ledmatrix.stream_fault_states(|_, _| {}).unwrap();
+    let _ = ledmatrix.led_open_at(len % 4, len / 4);
+});
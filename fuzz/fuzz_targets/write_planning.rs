@@ -0,0 +1,38 @@
+#![no_main]
+
+//! Planning of SPI transfers and chunked writes for any start, length and limit.
+
+use libfuzzer_sys::fuzz_target;
+use lp586x::{
+    interface::{plan_spi_writes, I2cInterface, RegisterAccess},
+    loopback::{LoopbackInterface, VirtualLp586x},
+    DataMode16Bit, Lp586x, Variant0,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 4 {
+        return;
+    }
+    let (header, payload) = data.split_at(4);
+    let start = u16::from_le_bytes([header[0], header[1]]);
+    let max_payload = u16::from_le_bytes([header[2], header[3]]) as usize;
+
+    let planned: usize = plan_spi_writes(start, payload, max_payload)
+        .map(|transfer| transfer.payload.len())
+        .sum();
+    assert_eq!(planned, payload.len());
+
+    if let Some(plan) =
+        Lp586x::<Variant0, (), DataMode16Bit>::plan_pwm_writes(start, payload, max_payload)
+    {
+        assert_eq!(
+            plan.map(|transfer| transfer.payload.len()).sum::<usize>(),
+            payload.len()
+        );
+    }
+
+    let _ = LoopbackInterface::new().write_registers(start, payload);
+
+    let mut i2c = I2cInterface::<_, 33>::with_max_transfer_size(VirtualLp586x::new(0x40), 0x40);
+    let _ = i2c.write_registers(start % 0x400, payload);
+});
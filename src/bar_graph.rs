@@ -0,0 +1,111 @@
+//! Level indicator on the single line of an LP5861.
+
+use crate::{interface::RegisterAccess, DataMode8Bit, Error, Lp586x, PwmAccess, Variant1};
+
+const NUM_SEGMENTS: usize = 18;
+
+/// How a level is shown by [`Lp5861BarGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarGraphMode {
+    /// All dots up to the level are lit
+    Bar,
+    /// Only the dot at the level is lit
+    Dot,
+}
+
+/// Bar graph on the 18 current sinks of an LP5861, with CS0 as the lowest segment.
+pub struct Lp5861BarGraph<I> {
+    driver: Lp586x<Variant1, I, DataMode8Bit>,
+    mode: BarGraphMode,
+    brightness: u8,
+}
+
+impl<I, IE> Lp5861BarGraph<I>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Maximum level, all segments lit.
+    pub const MAX_LEVEL: u8 = NUM_SEGMENTS as u8;
+
+    /// Create a bar graph on `driver`, lighting segments at full PWM.
+    pub fn new(driver: Lp586x<Variant1, I, DataMode8Bit>, mode: BarGraphMode) -> Self {
+        Self {
+            driver,
+            mode,
+            brightness: 0xff,
+        }
+    }
+
+    /// PWM value of lit segments.
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Change the display mode, takes effect on the next [`Lp5861BarGraph::set_level`].
+    pub fn set_mode(&mut self, mode: BarGraphMode) {
+        self.mode = mode;
+    }
+
+    /// Shows `level` (clamped to [`Self::MAX_LEVEL`]), 0 turns all segments off.
+    pub fn set_level(&mut self, level: u8) -> Result<(), Error<IE>> {
+        let level = level.min(Self::MAX_LEVEL) as usize;
+        let mut values = [0u8; NUM_SEGMENTS];
+
+        match self.mode {
+            BarGraphMode::Bar => values[..level].fill(self.brightness),
+            BarGraphMode::Dot if level > 0 => values[level - 1] = self.brightness,
+            BarGraphMode::Dot => {}
+        }
+
+        self.driver.set_pwm(0, &values)
+    }
+
+    /// Access the underlying driver, e.g. for setting dot currents.
+    pub fn driver(&mut self) -> &mut Lp586x<Variant1, I, DataMode8Bit> {
+        &mut self.driver
+    }
+
+    /// Destroys the bar graph and returns the driver.
+    pub fn into_inner(self) -> Lp586x<Variant1, I, DataMode8Bit> {
+        self.driver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861,
+    };
+
+    #[test]
+    fn test_set_level() {
+        let mut bar = vec![0x80; 5];
+        bar.extend([0; 13]);
+        let mut dot = vec![0; 18];
+        dot[17] = 0x80;
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, bar),
+            Access::WriteRegisters(0x200, dot),
+            Access::WriteRegisters(0x200, vec![0; 18]),
+        ]);
+
+        let ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let mut bar_graph = Lp5861BarGraph::new(ledmatrix, BarGraphMode::Bar).with_brightness(0x80);
+
+        bar_graph.set_level(5).unwrap();
+        bar_graph.set_mode(BarGraphMode::Dot);
+        bar_graph.set_level(30).unwrap();
+        bar_graph.set_level(0).unwrap();
+
+        bar_graph.into_inner().release().done();
+    }
+}
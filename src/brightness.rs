@@ -0,0 +1,885 @@
+//! Helpers for controlling the global brightness of the LED matrix.
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::RegisterAccess, register::Register, DataModeMarker, DeviceVariant, Error, Lp586x, Op,
+};
+
+/// Point on an ambient light to brightness curve.
+#[derive(Debug, Clone, Copy)]
+pub struct CurvePoint {
+    /// Ambient light level in lux
+    pub lux: u32,
+    /// Global brightness at this light level
+    pub brightness: u8,
+}
+
+/// Ambient light adaptive global brightness controller.
+///
+/// Feed periodic lux readings into [`AutoBrightness::update`]. The reading is mapped
+/// through a piecewise linear curve to a target brightness. The target is only
+/// re-evaluated when the light level moved more than the configured hysteresis from
+/// the last accepted reading, and the output ramps towards the target by at most the
+/// configured step per update.
+#[derive(Debug)]
+pub struct AutoBrightness<'a> {
+    curve: &'a [CurvePoint],
+    hysteresis: u32,
+    ramp_step: u8,
+    reference_lux: Option<u32>,
+    target: u8,
+    current: Option<u8>,
+}
+
+impl<'a> AutoBrightness<'a> {
+    /// Create a new controller from `curve`. Points have to be sorted by ascending
+    /// lux, and at least one point has to be given.
+    ///
+    /// # Panics
+    ///
+    /// If `curve` is empty or not sorted by lux.
+    pub fn new(curve: &'a [CurvePoint]) -> Self {
+        assert!(!curve.is_empty());
+        assert!(curve.windows(2).all(|w| w[0].lux <= w[1].lux));
+
+        Self {
+            curve,
+            hysteresis: 0,
+            ramp_step: u8::MAX,
+            reference_lux: None,
+            target: curve[0].brightness,
+            current: None,
+        }
+    }
+
+    /// Ignore light level changes up to `lux` relative to the last accepted reading.
+    pub fn with_hysteresis(mut self, lux: u32) -> Self {
+        self.hysteresis = lux;
+        self
+    }
+
+    /// Change the brightness by at most `step` per update. A `step` of 0 is treated as 1.
+    pub fn with_ramp_step(mut self, step: u8) -> Self {
+        self.ramp_step = step.max(1);
+        self
+    }
+
+    /// Brightness the controller is currently ramping towards.
+    pub fn target(&self) -> u8 {
+        self.target
+    }
+
+    /// Last brightness returned by [`AutoBrightness::update`], if any.
+    pub fn current(&self) -> Option<u8> {
+        self.current
+    }
+
+    /// Map `lux` on the curve, interpolating linearly between points and clamping
+    /// outside of the curve.
+    pub fn brightness_for(&self, lux: u32) -> u8 {
+        curve_value(self.curve, lux as i64, |point| {
+            (point.lux as i64, point.brightness)
+        })
+    }
+
+    /// Process a new lux reading. Returns the brightness to write when it changed
+    /// since the last update.
+    pub fn update(&mut self, lux: u32) -> Option<u8> {
+        let accept = match self.reference_lux {
+            Some(reference) => reference.abs_diff(lux) > self.hysteresis,
+            None => true,
+        };
+
+        if accept {
+            self.reference_lux = Some(lux);
+            self.target = self.brightness_for(lux);
+        }
+
+        let next = match self.current {
+            None => self.target,
+            Some(current) if current < self.target => {
+                current.saturating_add(self.ramp_step).min(self.target)
+            }
+            Some(current) => current.saturating_sub(self.ramp_step).max(self.target),
+        };
+
+        if self.current == Some(next) {
+            None
+        } else {
+            self.current = Some(next);
+            Some(next)
+        }
+    }
+
+    /// Process a new lux reading and write the resulting global brightness to `driver`
+    /// if it changed.
+    pub fn apply<DV, I, DM, IE>(
+        &mut self,
+        driver: &mut Lp586x<DV, I, DM>,
+        lux: u32,
+    ) -> Result<(), Error<IE>>
+    where
+        DV: DeviceVariant,
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        if let Some(brightness) = self.update(lux) {
+            driver.set_global_brightness(brightness)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Per product calibration mapping brightness percentages to register settings, so
+/// brightness sliders behave the same across panels.
+#[derive(Debug, Clone, Copy)]
+pub struct BrightnessCalibration {
+    /// Global brightness at 0%, 10%, .., 100%, e.g. measured to give evenly spaced
+    /// luminance steps. Values in between are interpolated linearly.
+    pub global_brightness: [u8; 11],
+    /// Group current scaling written alongside the global brightness, e.g. for white
+    /// balance.
+    pub group_currents: Option<[u8; 3]>,
+}
+
+impl BrightnessCalibration {
+    /// Global brightness proportional to the percentage, group currents untouched.
+    pub const LINEAR: Self = Self {
+        global_brightness: [0, 26, 51, 77, 102, 128, 153, 179, 204, 230, 255],
+        group_currents: None,
+    };
+
+    /// Global brightness for `percent` (clamped to 100).
+    pub fn global_brightness_for(&self, percent: u8) -> u8 {
+        let percent = percent.min(100) as u16;
+        let index = (percent / 10) as usize;
+        let lower = self.global_brightness[index] as i16;
+        let upper = self.global_brightness[(index + 1).min(10)] as i16;
+
+        (lower + (upper - lower) * (percent % 10) as i16 / 10) as u8
+    }
+}
+
+/// How [`LinkedBrightness`] distributes a single brightness level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkPolicy {
+    /// Global brightness follows the level, group brightness stays at the balance.
+    KeepColorBalance,
+    /// Global brightness stays at maximum, every group brightness is its balance
+    /// scaled by the level.
+    ScaleGroups,
+}
+
+/// Maps one logical brightness level to the global and all group brightness
+/// registers, e.g. for a single UI slider on a panel with color groups.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkedBrightness {
+    policy: LinkPolicy,
+    group_balance: [u8; 3],
+}
+
+impl LinkedBrightness {
+    /// Create a new mapping, with `group_balance` being the group brightness of
+    /// group 0, 1 and 2 at full brightness.
+    pub fn new(policy: LinkPolicy, group_balance: [u8; 3]) -> Self {
+        Self {
+            policy,
+            group_balance,
+        }
+    }
+
+    /// Global brightness followed by the brightness of group 0, 1 and 2 for `level`.
+    pub fn register_values(&self, level: u8) -> [u8; 4] {
+        match self.policy {
+            LinkPolicy::KeepColorBalance => {
+                let [group0, group1, group2] = self.group_balance;
+                [level, group0, group1, group2]
+            }
+            LinkPolicy::ScaleGroups => {
+                let scale = |balance: u8| (balance as u16 * level as u16 / 0xff) as u8;
+                let [group0, group1, group2] = self.group_balance.map(scale);
+                [0xff, group0, group1, group2]
+            }
+        }
+    }
+}
+
+/// Minutes in a day, the range of [`SchedulePoint::minute`]
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Brightness preset of a [`Schedule`], active from `minute` of the day on.
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulePoint {
+    /// Minute of the day (0..1440) the preset starts
+    pub minute: u16,
+    /// Global brightness of the preset
+    pub brightness: u8,
+}
+
+/// Time of day dependent brightness, e.g. dimming clocks and signage at night.
+///
+/// Every preset is active from its start until the start of the next one, wrapping
+/// around midnight. At the start of a preset the brightness changes linearly from the
+/// previous preset over the configured transition time. The time of day comes from
+/// the application, e.g. an RTC.
+#[derive(Debug, Clone)]
+pub struct Schedule<'a> {
+    points: &'a [SchedulePoint],
+    transition_minutes: u16,
+}
+
+impl<'a> Schedule<'a> {
+    /// Create a schedule of `points`, sorted by minute.
+    ///
+    /// # Panics
+    ///
+    /// If `points` is empty, not sorted by minute or holds a minute outside of
+    /// `0..MINUTES_PER_DAY`.
+    pub fn new(points: &'a [SchedulePoint]) -> Self {
+        assert!(!points.is_empty());
+        assert!(points.windows(2).all(|w| w[0].minute <= w[1].minute));
+        assert!(points.iter().all(|point| point.minute < MINUTES_PER_DAY));
+
+        Self {
+            points,
+            transition_minutes: 0,
+        }
+    }
+
+    /// Spreads every change of preset over `minutes`.
+    pub fn with_transition(mut self, minutes: u16) -> Self {
+        self.transition_minutes = minutes;
+        self
+    }
+
+    /// Brightness at `minute` of the day.
+    pub fn brightness_at(&self, minute: u16) -> u8 {
+        let minute = minute % MINUTES_PER_DAY;
+        let index = self
+            .points
+            .iter()
+            .rposition(|point| point.minute <= minute)
+            .unwrap_or(self.points.len() - 1);
+        let current = self.points[index];
+        let previous = self.points[(index + self.points.len() - 1) % self.points.len()];
+
+        let elapsed = (minute + MINUTES_PER_DAY - current.minute) % MINUTES_PER_DAY;
+        if elapsed >= self.transition_minutes {
+            return current.brightness;
+        }
+
+        interpolate(
+            elapsed as i64,
+            (0, previous.brightness),
+            (self.transition_minutes as i64, current.brightness),
+        )
+    }
+
+    /// Fades the global brightness of `driver` to the brightness at `minute` of the
+    /// day over `fade_us`, if it differs from the cached brightness of the driver.
+    /// Call periodically, e.g. once a minute.
+    pub fn apply<DV, I, DM, IE, D>(
+        &self,
+        driver: &mut Lp586x<DV, I, DM>,
+        minute: u16,
+        fade_us: u32,
+        delay: &mut D,
+    ) -> Result<(), Error<IE>>
+    where
+        DV: DeviceVariant,
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+        D: DelayUs<u32>,
+    {
+        let brightness = self.brightness_at(minute);
+        let current = driver.global_brightness();
+
+        if brightness != current {
+            driver.fade_global_brightness(current, brightness, fade_us, delay)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Point on a temperature derating curve.
+#[derive(Debug, Clone, Copy)]
+pub struct DeratingPoint {
+    /// Temperature in °C
+    pub temperature: i16,
+    /// Share of the nominal setting at this temperature, 255 being 100 %
+    pub scale: u8,
+}
+
+/// Setting reduced by a [`DeratingPolicy`], with its nominal value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeratingTarget {
+    /// Scale the global brightness
+    GlobalBrightness(u8),
+    /// Scale the currents of all three groups
+    GroupCurrents([u8; 3]),
+}
+
+/// Temperature dependent current derating, protecting dense panels in hot enclosures.
+///
+/// Feed periodic temperature readings into [`DeratingPolicy::apply`]. The reading is
+/// mapped through a piecewise linear curve (sorted by temperature) to a share of the
+/// nominal setting, which is only written when the resulting register values change.
+#[derive(Debug, Clone)]
+pub struct DeratingPolicy<'a> {
+    curve: &'a [DeratingPoint],
+    target: DeratingTarget,
+    group_currents: Option<[u8; 3]>,
+}
+
+impl<'a> DeratingPolicy<'a> {
+    /// Create a policy derating `target` along `curve`.
+    ///
+    /// # Panics
+    ///
+    /// If `curve` is empty or not sorted by temperature.
+    pub fn new(curve: &'a [DeratingPoint], target: DeratingTarget) -> Self {
+        assert!(!curve.is_empty());
+        assert!(curve
+            .windows(2)
+            .all(|w| w[0].temperature <= w[1].temperature));
+
+        Self {
+            curve,
+            target,
+            group_currents: None,
+        }
+    }
+
+    /// Share of the nominal setting at `temperature`, interpolating linearly between
+    /// points and clamping outside of the curve.
+    pub fn scale_for(&self, temperature: i16) -> u8 {
+        curve_value(self.curve, temperature as i64, |point| {
+            (point.temperature as i64, point.scale)
+        })
+    }
+
+    /// Derates the target of the policy on `driver` for `temperature`. The global
+    /// brightness is compared with the cached value of the driver, so changing it
+    /// elsewhere is corrected on the next call.
+    pub fn apply<DV, I, DM, IE>(
+        &mut self,
+        driver: &mut Lp586x<DV, I, DM>,
+        temperature: i16,
+    ) -> Result<(), Error<IE>>
+    where
+        DV: DeviceVariant,
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        let scale = self.scale_for(temperature) as u16;
+        let derate = |nominal: u8| (nominal as u16 * scale / 0xff) as u8;
+
+        match self.target {
+            DeratingTarget::GlobalBrightness(nominal) => {
+                let brightness = derate(nominal);
+                if driver.global_brightness() != brightness {
+                    driver.set_global_brightness(brightness)?;
+                }
+            }
+            DeratingTarget::GroupCurrents(nominal) => {
+                let currents = nominal.map(derate);
+                if self.group_currents != Some(currents) {
+                    driver.set_all_group_currents(currents)?;
+                    self.group_currents = Some(currents);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Value at `x` of a piecewise linear curve of `(x, value)` points sorted by `x`,
+/// clamped to the first and last point.
+fn curve_value<P>(curve: &[P], x: i64, point: impl Fn(&P) -> (i64, u8)) -> u8 {
+    let (Some(first), Some(last)) = (curve.first().map(&point), curve.last().map(&point)) else {
+        return 0;
+    };
+
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+
+    curve
+        .windows(2)
+        .map(|w| (point(&w[0]), point(&w[1])))
+        .find(|(_, upper)| x < upper.0)
+        .map_or(last.1, |(lower, upper)| interpolate(x, lower, upper))
+}
+
+/// Linear interpolation at `x` between the points `lower` and `upper`, with `x`
+/// clamped to the span of the points. The result always lies between both values.
+fn interpolate(x: i64, (x0, y0): (i64, u8), (x1, y1): (i64, u8)) -> u8 {
+    if x1 <= x0 {
+        return y1;
+    }
+
+    let x = x.clamp(x0, x1);
+    (y0 as i64 + (y1 as i64 - y0 as i64) * (x - x0) / (x1 - x0)) as u8
+}
+
+/// How a brightness level is realized, see [`Lp586x::set_dimmed_brightness`].
+///
+/// Digital dimming lowers the PWM duty cycle, which can flicker on camera at low
+/// levels. Analog dimming lowers the group currents instead and keeps the duty cycle,
+/// but only affects dots assigned to a group, and LED color may shift at low currents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimmingStrategy {
+    /// Scale the global brightness, group currents stay at full scale
+    Digital,
+    /// Scale the group currents, global brightness stays at maximum
+    Analog,
+    /// Scale the group currents down to `crossover`, below that scale the global
+    /// brightness
+    Hybrid { crossover: u8 },
+}
+
+impl DimmingStrategy {
+    /// Global brightness and group currents for `level`, given the group currents at
+    /// full brightness `full_currents`.
+    pub fn settings(&self, level: u8, full_currents: [u8; 3]) -> (u8, [u8; 3]) {
+        let scale = |value: u8, num: u8, den: u8| (value as u16 * num as u16 / den as u16) as u8;
+
+        match *self {
+            DimmingStrategy::Digital => (level, full_currents),
+            DimmingStrategy::Analog => (0xff, full_currents.map(|c| scale(c, level, 0xff))),
+            DimmingStrategy::Hybrid { crossover } if level >= crossover => {
+                (0xff, full_currents.map(|c| scale(c, level, 0xff)))
+            }
+            DimmingStrategy::Hybrid { crossover } => (
+                scale(0xff, level, crossover),
+                full_currents.map(|c| scale(c, crossover, 0xff)),
+            ),
+        }
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Sets the global brightness in percent (0..=100) of the maximum.
+    pub fn set_brightness_percent(&mut self, percent: u8) -> Result<(), Error<IE>> {
+        self.set_brightness_percent_calibrated(percent, &BrightnessCalibration::LINEAR)
+    }
+
+    /// Sets the brightness in percent (0..=100) using a product specific `calibration`.
+    pub fn set_brightness_percent_calibrated(
+        &mut self,
+        percent: u8,
+        calibration: &BrightnessCalibration,
+    ) -> Result<(), Error<IE>> {
+        if let Some(group_currents) = calibration.group_currents {
+            self.set_all_group_currents(group_currents)?;
+        }
+
+        self.set_global_brightness(calibration.global_brightness_for(percent))
+    }
+
+    /// Sets the global and all group brightness registers for `level` according to
+    /// `linked`, in a single transfer.
+    pub fn set_linked_brightness(
+        &mut self,
+        linked: &LinkedBrightness,
+        level: u8,
+    ) -> Result<(), Error<IE>> {
+        let values = linked.register_values(level);
+        self.interface
+            .write_registers(Register::GLOBAL_BRIGHTNESS, &values)
+            .map_err(|e| e.tagged(Op::Brightness))?;
+        self.settle_brightness(values[0]);
+
+        Ok(())
+    }
+
+    /// Sets the brightness to `level` using `strategy`, with `full_currents` being the
+    /// group currents at full brightness.
+    pub fn set_dimmed_brightness(
+        &mut self,
+        level: u8,
+        strategy: DimmingStrategy,
+        full_currents: [u8; 3],
+    ) -> Result<(), Error<IE>> {
+        let (global_brightness, group_currents) = strategy.settings(level, full_currents);
+
+        self.set_all_group_currents(group_currents)?;
+        self.set_global_brightness(global_brightness)
+    }
+
+    /// Maximum number of global brightness updates issued by a fade.
+    pub const FADE_STEPS: u32 = 32;
+
+    /// Fades the global brightness from `from` to `to` over `duration_us`.
+    pub fn fade_global_brightness<D: DelayUs<u32>>(
+        &mut self,
+        from: u8,
+        to: u8,
+        duration_us: u32,
+        delay: &mut D,
+    ) -> Result<(), Error<IE>> {
+        let distance = to as i32 - from as i32;
+        let steps = distance.unsigned_abs().clamp(1, Self::FADE_STEPS);
+
+        for step in 1..=steps {
+            delay.delay_us(duration_us / steps);
+            let brightness = from as i32 + distance * step as i32 / steps as i32;
+            self.set_global_brightness(brightness as u8)?;
+        }
+
+        Ok(())
+    }
+
+    /// Enables the chip with the global brightness at zero, then fades it in to
+    /// `brightness` over `duration_us`.
+    pub fn enable_with_fade<D: DelayUs<u32>>(
+        &mut self,
+        brightness: u8,
+        duration_us: u32,
+        delay: &mut D,
+    ) -> Result<(), Error<IE>> {
+        self.set_global_brightness(0)?;
+        self.chip_enable(true)?;
+        delay.delay_us(Self::T_CHIP_EN_US);
+
+        self.fade_global_brightness(0, brightness, duration_us, delay)
+    }
+
+    /// Fades the global brightness from `brightness` to zero over `duration_us`, then
+    /// disables the chip.
+    pub fn disable_with_fade<D: DelayUs<u32>>(
+        &mut self,
+        brightness: u8,
+        duration_us: u32,
+        delay: &mut D,
+    ) -> Result<(), Error<IE>> {
+        self.fade_global_brightness(brightness, 0, duration_us, delay)?;
+
+        self.chip_enable(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface, NoDelay},
+        Lp5861, PwmAccess,
+    };
+
+    const CURVE: [CurvePoint; 3] = [
+        CurvePoint {
+            lux: 0,
+            brightness: 10,
+        },
+        CurvePoint {
+            lux: 100,
+            brightness: 110,
+        },
+        CurvePoint {
+            lux: 1000,
+            brightness: 255,
+        },
+    ];
+
+    #[test]
+    fn test_curve_interpolation() {
+        let auto_brightness = AutoBrightness::new(&CURVE);
+
+        assert_eq!(auto_brightness.brightness_for(0), 10);
+        assert_eq!(auto_brightness.brightness_for(50), 60);
+        assert_eq!(auto_brightness.brightness_for(100), 110);
+        assert_eq!(auto_brightness.brightness_for(5000), 255);
+    }
+
+    #[test]
+    fn test_hysteresis_and_ramp() {
+        let mut auto_brightness = AutoBrightness::new(&CURVE)
+            .with_hysteresis(10)
+            .with_ramp_step(20);
+
+        assert_eq!(auto_brightness.update(0), Some(10));
+        // within hysteresis, nothing changes
+        assert_eq!(auto_brightness.update(8), None);
+        // ramp up towards 60 in steps of 20
+        assert_eq!(auto_brightness.update(50), Some(30));
+        assert_eq!(auto_brightness.update(50), Some(50));
+        assert_eq!(auto_brightness.update(50), Some(60));
+        assert_eq!(auto_brightness.update(50), None);
+    }
+
+    #[test]
+    fn test_brightness_percent() {
+        let calibration = BrightnessCalibration {
+            global_brightness: [0, 2, 5, 10, 20, 35, 55, 85, 125, 180, 255],
+            group_currents: Some([0x7f, 0x60, 0x70]),
+        };
+
+        assert_eq!(BrightnessCalibration::LINEAR.global_brightness_for(50), 128);
+        assert_eq!(
+            BrightnessCalibration::LINEAR.global_brightness_for(200),
+            255
+        );
+        assert_eq!(calibration.global_brightness_for(95), 217);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 26),
+            Access::WriteRegisters(0x009, vec![0x7f, 0x60, 0x70]),
+            Access::WriteRegister(0x005, 20),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        ledmatrix.set_brightness_percent(10).unwrap();
+        ledmatrix
+            .set_brightness_percent_calibrated(40, &calibration)
+            .unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_linked_brightness() {
+        let balanced = LinkedBrightness::new(LinkPolicy::KeepColorBalance, [0xff, 0xc0, 0x80]);
+        let scaled = LinkedBrightness::new(LinkPolicy::ScaleGroups, [0xff, 0xc0, 0x80]);
+
+        assert_eq!(scaled.register_values(0), [0xff, 0, 0, 0]);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x005, vec![0x40, 0xff, 0xc0, 0x80]),
+            Access::WriteRegisters(0x005, vec![0xff, 0x80, 0x60, 0x40]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        ledmatrix.set_linked_brightness(&balanced, 0x40).unwrap();
+        ledmatrix.set_linked_brightness(&scaled, 0x80).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_linked_brightness_keeps_target() {
+        let balanced = LinkedBrightness::new(LinkPolicy::KeepColorBalance, [0xff, 0xc0, 0x80]);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x005, vec![0x40, 0xff, 0xc0, 0x80]),
+            Access::WriteRegisters(0x200, vec![1]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .with_brightness_slew_limit(0x10)
+            .into_8bit_data_mode()
+            .unwrap();
+        ledmatrix.set_linked_brightness(&balanced, 0x40).unwrap();
+        assert_eq!(ledmatrix.brightness_target(), 0x40);
+        ledmatrix.set_pwm(0, &[1u8]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_schedule() {
+        let points = [
+            SchedulePoint {
+                minute: 7 * 60,
+                brightness: 0xff,
+            },
+            SchedulePoint {
+                minute: 22 * 60,
+                brightness: 0x1f,
+            },
+        ];
+        let schedule = Schedule::new(&points).with_transition(30);
+
+        assert_eq!(schedule.brightness_at(3 * 60), 0x1f);
+        assert_eq!(schedule.brightness_at(7 * 60), 0x1f);
+        assert_eq!(schedule.brightness_at(7 * 60 + 15), 0x8f);
+        assert_eq!(schedule.brightness_at(12 * 60), 0xff);
+        assert_eq!(schedule.brightness_at(22 * 60 + 30), 0x1f);
+        assert_eq!(schedule.brightness_at(MINUTES_PER_DAY + 12 * 60), 0xff);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0xfe),
+            Access::WriteRegister(0x005, 0xfd),
+        ]);
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+
+        schedule
+            .apply(&mut ledmatrix, 12 * 60, 0, &mut NoDelay)
+            .unwrap();
+        let points = [SchedulePoint {
+            minute: 0,
+            brightness: 0xfd,
+        }];
+        let schedule = Schedule::new(&points);
+        schedule
+            .apply(&mut ledmatrix, 2 * 60, 0, &mut NoDelay)
+            .unwrap();
+        schedule
+            .apply(&mut ledmatrix, 3 * 60, 0, &mut NoDelay)
+            .unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_derating_policy() {
+        let curve = [
+            DeratingPoint {
+                temperature: 60,
+                scale: 0xff,
+            },
+            DeratingPoint {
+                temperature: 80,
+                scale: 0x7f,
+            },
+        ];
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0x80),
+            Access::WriteRegister(0x005, 0x3f),
+            Access::WriteRegisters(0x009, vec![0x40, 0x20, 0x10]),
+            Access::WriteRegisters(0x009, vec![0x2f, 0x17, 0x0b]),
+        ]);
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+
+        let mut policy = DeratingPolicy::new(&curve, DeratingTarget::GlobalBrightness(0x80));
+        assert_eq!(policy.scale_for(70), 0xbf);
+        assert_eq!(policy.scale_for(i16::MAX), 0x7f);
+        assert_eq!(policy.scale_for(i16::MIN), 0xff);
+        policy.apply(&mut ledmatrix, 20).unwrap();
+        policy.apply(&mut ledmatrix, 90).unwrap();
+
+        let mut policy =
+            DeratingPolicy::new(&curve, DeratingTarget::GroupCurrents([0x40, 0x20, 0x10]));
+        policy.apply(&mut ledmatrix, 25).unwrap();
+        policy.apply(&mut ledmatrix, 60).unwrap();
+        policy.apply(&mut ledmatrix, 70).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_schedule_minute_out_of_range() {
+        let points = [SchedulePoint {
+            minute: MINUTES_PER_DAY,
+            brightness: 0xff,
+        }];
+
+        Schedule::new(&points);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_schedule_unsorted() {
+        let points = [
+            SchedulePoint {
+                minute: 22 * 60,
+                brightness: 0x1f,
+            },
+            SchedulePoint {
+                minute: 7 * 60,
+                brightness: 0xff,
+            },
+        ];
+
+        Schedule::new(&points);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_derating_unsorted_curve() {
+        let curve = [
+            DeratingPoint {
+                temperature: 80,
+                scale: 0x7f,
+            },
+            DeratingPoint {
+                temperature: -40,
+                scale: 0xff,
+            },
+        ];
+
+        DeratingPolicy::new(&curve, DeratingTarget::GlobalBrightness(0xff));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(interpolate(5, (0, 0), (10, 200)), 100);
+        assert_eq!(interpolate(-5, (0, 0xff), (10, 0)), 0xff);
+        assert_eq!(interpolate(i64::MAX, (0, 0xff), (10, 0)), 0);
+        assert_eq!(interpolate(3, (4, 1), (4, 2)), 2);
+    }
+
+    #[test]
+    fn test_dimming_strategy() {
+        let full = [0x7f, 0x60, 0x40];
+        let hybrid = DimmingStrategy::Hybrid { crossover: 0x40 };
+
+        assert_eq!(DimmingStrategy::Digital.settings(0x10, full), (0x10, full));
+        assert_eq!(
+            DimmingStrategy::Analog.settings(0x80, full),
+            (0xff, [0x3f, 0x30, 0x20])
+        );
+        assert_eq!(hybrid.settings(0x80, full), (0xff, [0x3f, 0x30, 0x20]));
+        assert_eq!(hybrid.settings(0x20, full), (0x7f, [0x1f, 0x18, 0x10]));
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x009, vec![0x3f, 0x30, 0x20]),
+            Access::WriteRegister(0x005, 0xff),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        ledmatrix
+            .set_dimmed_brightness(0x80, DimmingStrategy::Analog, full)
+            .unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_enable_with_fade() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 1),
+            Access::WriteRegister(0x005, 2),
+            Access::WriteRegister(0x005, 3),
+            Access::WriteRegister(0x005, 1),
+            Access::WriteRegister(0x005, 0),
+            Access::WriteRegister(0x000, 0),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        ledmatrix.enable_with_fade(3, 1000, &mut NoDelay).unwrap();
+        ledmatrix.disable_with_fade(2, 1000, &mut NoDelay).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
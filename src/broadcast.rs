@@ -0,0 +1,123 @@
+//! Write-only access to a chain of controllers sharing one SPI bus and CS line.
+//!
+//! With MOSI, SCLK and CS of several LP586x tied together, every write reaches all
+//! controllers at once, while reads would have all of them drive MISO. Wrapping the SPI
+//! interface in a [`BroadcastInterface`] makes the driver address the whole chain as a
+//! single controller and turns every read into [`Error::ReadUnsupported`].
+
+use crate::{
+    configuration::Configuration, interface::RegisterAccess, DataModeMarker, DeviceVariant, Error,
+    Lp586x, PwmAccess,
+};
+
+/// Interface writing to all controllers of a chain, see the [module docs](self).
+///
+/// Driver functions reading registers (e.g. fault detection or read-modify-write
+/// updates) fail with [`Error::ReadUnsupported`] without touching the bus.
+#[derive(Debug)]
+pub struct BroadcastInterface<I> {
+    interface: I,
+}
+
+impl<I> BroadcastInterface<I> {
+    /// Wraps `interface`, whose CS line selects all controllers of the chain.
+    pub fn new(interface: I) -> Self {
+        Self { interface }
+    }
+
+    /// Destroys the wrapper and returns the interface.
+    pub fn release(self) -> I {
+        self.interface
+    }
+}
+
+impl<I, IE> RegisterAccess for BroadcastInterface<I>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    type Error = Error<IE>;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        self.interface.max_recommended_bus_hz()
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        self.interface.max_contiguous_write(start_register)
+    }
+
+    fn max_contiguous_read(&self, _start_register: u16) -> usize {
+        0
+    }
+
+    fn read_registers(
+        &mut self,
+        _start_register: u16,
+        _data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        Err(Error::ReadUnsupported)
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        self.interface.write_registers(start_register, data)
+    }
+}
+
+impl<DV, I, DM, IE> Lp586x<DV, BroadcastInterface<I>, DM>
+where
+    DV: DeviceVariant,
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Configures all controllers of the chain and writes `frame`, starting at dot 0,
+    /// to all of them. Every register block is sent once for the whole chain.
+    pub fn apply_to_chain<T>(
+        &mut self,
+        configuration: &Configuration,
+        frame: &[T],
+    ) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.configure(configuration)?;
+        self.set_pwm(0, frame)
+    }
+
+    /// Destroys the driver and returns the broadcast interface.
+    pub fn release(self) -> BroadcastInterface<I> {
+        self.interface
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_broadcast_interface() {
+        let configuration = Configuration::new::<Variant1>();
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegisters(0x200, vec![1, 2, 3]),
+            Access::WriteRegister(0x005, 0),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegister(0x005, 0xff),
+        ]);
+
+        let mut chain = Lp5861::new(BroadcastInterface::new(interface))
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        chain.apply_to_chain(&configuration, &[1u8, 2, 3]).unwrap();
+        assert!(matches!(chain.get_pwm(0), Err(Error::ReadUnsupported)));
+
+        chain.live_reconfigure(&configuration).unwrap();
+
+        chain.release().release().done();
+    }
+}
@@ -0,0 +1,481 @@
+//! Dot current calibration, e.g. from per LED bin data gathered in production, and
+//! brightness response measurement for optical calibration rigs.
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::RegisterAccess, register::Register, DataModeMarker, DeviceVariant, Dot, Error,
+    Lp586x, Op, PwmAccess, MAX_DOTS,
+};
+
+/// Dot currents for any subset of the dots, built up one dot at a time.
+///
+/// Only dots with a value are written by [`DotCurrentTable::apply`], with consecutive
+/// dots combined into a single transfer.
+#[derive(Debug, Clone)]
+pub struct DotCurrentTable<DV> {
+    currents: [u8; MAX_DOTS],
+    present: [bool; MAX_DOTS],
+    _phantom_data: core::marker::PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant> DotCurrentTable<DV> {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self {
+            currents: [0; MAX_DOTS],
+            present: [false; MAX_DOTS],
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    /// Sets the current of `dot`.
+    pub fn set(&mut self, dot: Dot<DV>, current: u8) {
+        self.currents[dot.index() as usize] = current;
+        self.present[dot.index() as usize] = true;
+    }
+
+    /// Current of `dot`, if set.
+    pub fn get(&self, dot: u16) -> Option<u8> {
+        let dot = dot as usize;
+        (dot < DV::NUM_DOTS as usize && self.present[dot]).then(|| self.currents[dot])
+    }
+
+    /// Consecutive runs of set dots, as start dot and currents.
+    pub fn runs(&self) -> impl Iterator<Item = (u16, &[u8])> + '_ {
+        let num_dots = DV::NUM_DOTS as usize;
+        let mut dot = 0;
+
+        core::iter::from_fn(move || {
+            while dot < num_dots && !self.present[dot] {
+                dot += 1;
+            }
+            let start = dot;
+            while dot < num_dots && self.present[dot] {
+                dot += 1;
+            }
+
+            (start < dot).then(|| (start as u16, &self.currents[start..dot]))
+        })
+    }
+
+    /// Writes all dot currents set in the table to `driver`, one transfer per run of
+    /// consecutive dots.
+    pub fn apply<I, DM, IE>(&self, driver: &mut Lp586x<DV, I, DM>) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        for (start_dot, currents) in self.runs() {
+            driver.apply_dot_current_table(currents, start_dot)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DV: DeviceVariant> Default for DotCurrentTable<DV> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Writes the dot currents `table` of consecutive dots starting at `start_dot`.
+    /// Unlike [`Lp586x::set_dot_current`], the table is validated to fit the device
+    /// variant before anything is written, regardless of the [`crate::OverflowBehavior`].
+    pub fn apply_dot_current_table(
+        &mut self,
+        table: &[u8],
+        start_dot: u16,
+    ) -> Result<(), Error<IE>> {
+        if table.is_empty() || start_dot as usize + table.len() > DV::NUM_DOTS as usize {
+            return Err(Error::DotOutOfRange);
+        }
+
+        self.interface
+            .write_registers(Register::dot_current(start_dot), table)
+            .map_err(|e| e.tagged(Op::DotSettings))
+    }
+}
+
+/// Version of the stored dot current format written by [`DotCurrentBlob::encode`]
+pub const DOT_CURRENT_FORMAT_VERSION: u8 = 1;
+
+/// Bytes of a stored dot current table besides the currents: version, number of
+/// lines, start dot, number of dots and checksum.
+pub const DOT_CURRENT_FORMAT_OVERHEAD: usize = 7;
+
+/// Reason stored dot currents were rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// Data or buffer shorter than the table
+    TooShort,
+    /// Written with an unknown format version
+    UnknownVersion(u8),
+    /// Written for a device variant with a different number of lines
+    WrongVariant,
+    /// Checksum mismatch, e.g. from corrupted flash
+    BadChecksum,
+    /// Dots outside of the device variant
+    DotOutOfRange,
+}
+
+/// Error of [`Lp586x::apply_dot_currents_from_slice`]
+#[derive(Debug)]
+pub enum CalibrationError<IE> {
+    /// Stored data rejected
+    Format(FormatError),
+    /// Driver error
+    Driver(Error<IE>),
+}
+
+/// Dot currents of consecutive dots in a compact format for MCU flash or EEPROM:
+/// version, number of lines of the variant, start dot and number of dots (little
+/// endian), the currents and a CRC-8 over all of it.
+///
+/// Encoding is deterministic, so comparing a freshly encoded table with the stored one
+/// avoids needless erase cycles.
+#[derive(Debug, Clone, Copy)]
+pub struct DotCurrentBlob<'a> {
+    start_dot: u16,
+    currents: &'a [u8],
+}
+
+impl<'a> DotCurrentBlob<'a> {
+    /// Encodes `currents` of consecutive dots starting at `start_dot` into `buffer`,
+    /// returning the number of bytes used.
+    pub fn encode<DV: DeviceVariant>(
+        start_dot: u16,
+        currents: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<usize, FormatError> {
+        if start_dot as usize + currents.len() > DV::NUM_DOTS as usize {
+            return Err(FormatError::DotOutOfRange);
+        }
+        let len = currents.len() + DOT_CURRENT_FORMAT_OVERHEAD;
+        let buffer = buffer.get_mut(..len).ok_or(FormatError::TooShort)?;
+
+        buffer[0] = DOT_CURRENT_FORMAT_VERSION;
+        buffer[1] = DV::NUM_LINES;
+        buffer[2..4].copy_from_slice(&start_dot.to_le_bytes());
+        buffer[4..6].copy_from_slice(&(currents.len() as u16).to_le_bytes());
+        buffer[6..len - 1].copy_from_slice(currents);
+        buffer[len - 1] = crc8(&buffer[..len - 1]);
+
+        Ok(len)
+    }
+
+    /// Validates stored `data` for device variant `DV`.
+    pub fn parse<DV: DeviceVariant>(data: &'a [u8]) -> Result<Self, FormatError> {
+        let header = data.get(..6).ok_or(FormatError::TooShort)?;
+        if header[0] != DOT_CURRENT_FORMAT_VERSION {
+            return Err(FormatError::UnknownVersion(header[0]));
+        }
+        if header[1] != DV::NUM_LINES {
+            return Err(FormatError::WrongVariant);
+        }
+
+        let start_dot = u16::from_le_bytes([header[2], header[3]]);
+        let count = u16::from_le_bytes([header[4], header[5]]) as usize;
+        let len = count + DOT_CURRENT_FORMAT_OVERHEAD;
+        let data = data.get(..len).ok_or(FormatError::TooShort)?;
+
+        if crc8(&data[..len - 1]) != data[len - 1] {
+            return Err(FormatError::BadChecksum);
+        }
+        if start_dot as usize + count > DV::NUM_DOTS as usize {
+            return Err(FormatError::DotOutOfRange);
+        }
+
+        Ok(Self {
+            start_dot,
+            currents: &data[6..len - 1],
+        })
+    }
+
+    /// First dot of the table.
+    pub fn start_dot(&self) -> u16 {
+        self.start_dot
+    }
+
+    /// Currents of consecutive dots starting at [`Self::start_dot`].
+    pub fn currents(&self) -> &'a [u8] {
+        self.currents
+    }
+}
+
+/// CRC-8 with polynomial 0x07
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Validates dot currents stored with [`DotCurrentBlob::encode`] and writes them in
+    /// a single register write, e.g. at boot. Nothing is written if `data` is rejected.
+    pub fn apply_dot_currents_from_slice(
+        &mut self,
+        data: &[u8],
+    ) -> Result<(), CalibrationError<IE>> {
+        let blob = DotCurrentBlob::parse::<DV>(data).map_err(CalibrationError::Format)?;
+        if blob.currents.is_empty() {
+            return Ok(());
+        }
+
+        self.apply_dot_current_table(blob.currents, blob.start_dot)
+            .map_err(CalibrationError::Driver)
+    }
+}
+
+/// Measured brightness response of a dot, see [`Lp586x::measure_response`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCurve<const N: usize> {
+    /// PWM values stepped through
+    pub pwm: [u16; N],
+    /// Measurement taken at each of the PWM values
+    pub response: [u32; N],
+}
+
+impl<const N: usize> ResponseCurve<N> {
+    /// Table of `M` PWM values producing a linear response: entry `i` is the PWM value
+    /// expected to reach `i / (M - 1)` of the highest measured response, interpolated
+    /// linearly between the measured points. The PWM values have to be ascending (as
+    /// returned by [`Lp586x::measure_response`]) and the response has to increase with
+    /// them, otherwise the table is meaningless.
+    pub fn lut<const M: usize>(&self) -> [u16; M] {
+        let max_response = self.response.iter().copied().max().unwrap_or(0) as u64;
+        let mut lut = [0; M];
+
+        for (i, entry) in lut.iter_mut().enumerate() {
+            let target = max_response * i as u64 / (M as u64 - 1).max(1);
+
+            *entry = match self.response.iter().position(|&r| r as u64 >= target) {
+                Some(0) => self.pwm[0],
+                Some(k) => {
+                    let (p0, p1) = (self.pwm[k - 1] as u64, self.pwm[k] as u64);
+                    let (r0, r1) = (self.response[k - 1] as u64, self.response[k] as u64);
+                    let step = p1.saturating_sub(p0) * target.saturating_sub(r0);
+                    (p0 + step / r1.saturating_sub(r0).max(1)).min(u16::MAX as u64) as u16
+                }
+                None => self.pwm.last().copied().unwrap_or(0),
+            };
+        }
+
+        lut
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Steps `dot` through the PWM values `levels`, waiting `settle_us` at each step
+    /// before calling `measure`, e.g. reading a light sensor. The PWM value of the dot
+    /// is restored afterwards. The returned curve is sorted by ascending PWM value, so
+    /// `levels` may be given in any order, e.g. sweeping from bright to dark.
+    pub fn measure_response<T, D, const N: usize>(
+        &mut self,
+        dot: u16,
+        levels: [T; N],
+        settle_us: u32,
+        delay: &mut D,
+        mut measure: impl FnMut() -> u32,
+    ) -> Result<ResponseCurve<N>, Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+        T: Copy + Into<u16>,
+        D: DelayUs<u32>,
+    {
+        let previous = self.get_pwm(dot)?;
+        let mut response = [0; N];
+
+        for (&level, response) in levels.iter().zip(&mut response) {
+            self.set_pwm(dot, &[level])?;
+            delay.delay_us(settle_us);
+            *response = measure();
+        }
+
+        self.set_pwm(dot, &[previous])?;
+
+        let mut pairs = [(0, 0); N];
+        pairs
+            .iter_mut()
+            .zip(levels.iter().zip(response))
+            .for_each(|(pair, (&level, response))| *pair = (level.into(), response));
+        pairs.sort_unstable_by_key(|&(pwm, _)| pwm);
+
+        Ok(ResponseCurve {
+            pwm: pairs.map(|(pwm, _)| pwm),
+            response: pairs.map(|(_, response)| response),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface, NoDelay},
+        Lp5862, Variant2,
+    };
+
+    #[test]
+    fn test_measure_response() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x200 + 5, 0x42),
+            Access::WriteRegisters(0x200 + 5, vec![0]),
+            Access::WriteRegisters(0x200 + 5, vec![128]),
+            Access::WriteRegisters(0x200 + 5, vec![255]),
+            Access::WriteRegisters(0x200 + 5, vec![0x42]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        // a sensor seeing a quarter of the light at half the PWM value
+        let mut readings = [0, 100, 400].into_iter();
+        let curve = ledmatrix
+            .measure_response(5, [0u8, 128, 255], 1000, &mut NoDelay, || {
+                readings.next().unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(curve.pwm, [0, 128, 255]);
+        assert_eq!(curve.response, [0, 100, 400]);
+        assert_eq!(curve.lut::<5>(), [0, 128, 170, 212, 255]);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_measure_response_descending() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x200 + 5, 0x42),
+            Access::WriteRegisters(0x200 + 5, vec![255]),
+            Access::WriteRegisters(0x200 + 5, vec![128]),
+            Access::WriteRegisters(0x200 + 5, vec![0]),
+            Access::WriteRegisters(0x200 + 5, vec![0x42]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        let mut readings = [400, 100, 0].into_iter();
+        let curve = ledmatrix
+            .measure_response(5, [255u8, 128, 0], 1000, &mut NoDelay, || {
+                readings.next().unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(curve.pwm, [0, 128, 255]);
+        assert_eq!(curve.response, [0, 100, 400]);
+        assert_eq!(curve.lut::<5>(), [0, 128, 170, 212, 255]);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_dot_current_blob() {
+        let mut buffer = [0u8; 16];
+        let len = DotCurrentBlob::encode::<Variant2>(34, &[0x40, 0x41], &mut buffer).unwrap();
+        assert_eq!(len, 9);
+        assert_eq!(buffer[..8], [1, 2, 34, 0, 2, 0, 0x40, 0x41]);
+
+        assert!(matches!(
+            DotCurrentBlob::encode::<Variant2>(35, &[0x40, 0x41], &mut buffer),
+            Err(FormatError::DotOutOfRange)
+        ));
+        assert_eq!(
+            DotCurrentBlob::parse::<crate::Variant4>(&buffer[..len]).unwrap_err(),
+            FormatError::WrongVariant
+        );
+        assert_eq!(
+            DotCurrentBlob::parse::<Variant2>(&buffer[..len - 1]).unwrap_err(),
+            FormatError::TooShort
+        );
+
+        let mut corrupted = buffer;
+        corrupted[6] ^= 1;
+        assert_eq!(
+            DotCurrentBlob::parse::<Variant2>(&corrupted[..len]).unwrap_err(),
+            FormatError::BadChecksum
+        );
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x100 + 34, vec![0x40, 0x41]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        ledmatrix
+            .apply_dot_currents_from_slice(&buffer[..len])
+            .unwrap();
+        assert!(matches!(
+            ledmatrix.apply_dot_currents_from_slice(&corrupted[..len]),
+            Err(CalibrationError::Format(FormatError::BadChecksum))
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_dot_current_table() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x100 + 3, vec![0x10, 0x11]),
+            Access::WriteRegisters(0x100 + 35, vec![0x20]),
+            Access::WriteRegisters(0x100 + 34, vec![1, 2]),
+            Access::FailWrite(0x100),
+        ]);
+
+        let mut table = DotCurrentTable::<Variant2>::new();
+        table.set(Dot::with_index(4), 0x11);
+        table.set(Dot::with_index(35), 0x20);
+        table.set(Dot::with_index(3), 0x10);
+        assert_eq!(table.get(4), Some(0x11));
+        assert_eq!(table.get(5), None);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        table.apply(&mut ledmatrix).unwrap();
+        ledmatrix.apply_dot_current_table(&[1, 2], 34).unwrap();
+        assert!(matches!(
+            ledmatrix.apply_dot_current_table(&[1, 2, 3], 34),
+            Err(Error::DotOutOfRange)
+        ));
+        assert!(matches!(
+            ledmatrix.apply_dot_current_table(&[1], 0),
+            Err(Error::Interface {
+                op: Op::DotSettings,
+                ..
+            })
+        ));
+
+        ledmatrix.release().done();
+    }
+}
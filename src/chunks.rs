@@ -0,0 +1,72 @@
+//! Splitting transfers of consecutive registers into chunks fitting interface limits.
+
+/// Splits a transfer of consecutive registers into chunks, yielding the start register
+/// of each chunk together with its range in the transferred data.
+pub(crate) struct RegisterChunks<F> {
+    register: u16,
+    offset: usize,
+    len: usize,
+    max_chunk_len: F,
+}
+
+/// Chunks for `len` registers starting at `start_register`, each at most
+/// `max_chunk_len(chunk_start_register)` (but at least one) registers long.
+///
+/// `max_chunk_len` expresses the interface limits, e.g. the transfer buffer size or
+/// the number of registers left in an I2C register page.
+pub(crate) fn chunks_for_registers<F: Fn(u16) -> usize>(
+    start_register: u16,
+    len: usize,
+    max_chunk_len: F,
+) -> RegisterChunks<F> {
+    RegisterChunks {
+        register: start_register,
+        offset: 0,
+        len,
+        max_chunk_len,
+    }
+}
+
+impl<F: Fn(u16) -> usize> Iterator for RegisterChunks<F> {
+    type Item = (u16, core::ops::Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.len {
+            return None;
+        }
+
+        let chunk_len = (self.max_chunk_len)(self.register).clamp(1, self.len - self.offset);
+        let chunk = (self.register, self.offset..self.offset + chunk_len);
+
+        self.register = self.register.wrapping_add(chunk_len as u16);
+        self.offset += chunk_len;
+
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_for_registers() {
+        // fixed limit
+        let chunks: Vec<_> = chunks_for_registers(0x200, 5, |_| 2).collect();
+        assert_eq!(chunks, vec![(0x200, 0..2), (0x202, 2..4), (0x204, 4..5)]);
+
+        // page boundaries every 0x100 registers
+        let page_limit = |register: u16| 0x100 - (register as usize % 0x100);
+        let chunks: Vec<_> = chunks_for_registers(0x0fe, 0x103, page_limit).collect();
+        assert_eq!(
+            chunks,
+            vec![(0x0fe, 0..2), (0x100, 2..0x102), (0x200, 0x102..0x103)]
+        );
+
+        // a limit of zero still makes progress
+        let chunks: Vec<_> = chunks_for_registers(0x000, 2, |_| 0).collect();
+        assert_eq!(chunks, vec![(0x000, 0..1), (0x001, 1..2)]);
+
+        assert_eq!(chunks_for_registers(0x000, 0, |_| 4).count(), 0);
+    }
+}
@@ -0,0 +1,201 @@
+//! Clock face showing HH:MM with a built-in 3x5 font, sized for the 18 current sinks
+//! of a line.
+
+use crate::{framebuffer::Framebuffer, DeviceVariant};
+
+/// Digits 0 to 9, five rows of three columns each, MSB left
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// First column of the four digits, relative to the clock face
+const DIGIT_COLUMNS: [u8; 4] = [0, 4, 10, 14];
+
+/// Column of the colon, relative to the clock face
+const COLON_COLUMN: u8 = 8;
+
+/// Horizontal placement of a [`ClockFace`] within the lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+/// HH:MM clock rendered into a [`Framebuffer`], five lines high and
+/// [`ClockFace::WIDTH`] current sinks wide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockFace {
+    top_line: u8,
+    alignment: Alignment,
+    leading_zero: bool,
+    blinking_colon: bool,
+}
+
+impl ClockFace {
+    /// Width of the clock face in current sinks.
+    pub const WIDTH: u8 = 17;
+    /// Height of the clock face in lines.
+    pub const HEIGHT: u8 = 5;
+
+    /// Centered clock face on the first five lines, with a leading zero and a steady
+    /// colon.
+    pub fn new() -> Self {
+        Self {
+            top_line: 0,
+            alignment: Alignment::Center,
+            leading_zero: true,
+            blinking_colon: false,
+        }
+    }
+
+    /// Line of the top row of the digits.
+    pub fn with_top_line(mut self, top_line: u8) -> Self {
+        self.top_line = top_line;
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Shows hours below 10 with a leading zero (`09:30`) or without (` 9:30`).
+    pub fn with_leading_zero(mut self, leading_zero: bool) -> Self {
+        self.leading_zero = leading_zero;
+        self
+    }
+
+    /// Shows the colon on even `seconds` only, so it blinks once per second.
+    pub fn with_blinking_colon(mut self, blinking_colon: bool) -> Self {
+        self.blinking_colon = blinking_colon;
+        self
+    }
+
+    /// First current sink of the clock face on a variant with `current_sinks` sinks.
+    fn left_column(&self, current_sinks: u8) -> u8 {
+        let free = current_sinks.saturating_sub(Self::WIDTH);
+
+        match self.alignment {
+            Alignment::Left => 0,
+            Alignment::Center => free / 2,
+            Alignment::Right => free,
+        }
+    }
+
+    /// Renders `hours` (0..=99) and `minutes` (0..=59) into `framebuffer`, dots of the
+    /// clock face that are lit set to `on`, all others turned off. Dots outside the
+    /// clock face are left unchanged, parts beyond the last line are clipped.
+    pub fn render<DV: DeviceVariant, T: Copy + Default + PartialEq>(
+        &self,
+        framebuffer: &mut Framebuffer<DV, T>,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        on: T,
+    ) {
+        let hours = hours.min(99);
+        let minutes = minutes.min(59);
+        let digits = [
+            (hours >= 10 || self.leading_zero).then_some(hours / 10),
+            Some(hours % 10),
+            Some(minutes / 10),
+            Some(minutes % 10),
+        ];
+        let colon = !self.blinking_colon || seconds & 1 == 0;
+        let left = self.left_column(DV::NUM_CURRENT_SINKS);
+
+        for row in 0..Self::HEIGHT {
+            let line = self.top_line.saturating_add(row);
+
+            for column in 0..Self::WIDTH {
+                let lit = match DIGIT_COLUMNS
+                    .iter()
+                    .position(|&start| (start..start + 3).contains(&column))
+                {
+                    Some(digit) => digits[digit].is_some_and(|value| {
+                        let bits = DIGITS[value as usize][row as usize];
+                        bits & (0b100 >> (column - DIGIT_COLUMNS[digit])) != 0
+                    }),
+                    None => column == COLON_COLUMN && colon && (row == 1 || row == 3),
+                };
+
+                let value = if lit { on } else { T::default() };
+                framebuffer.set_at(line, left + column, value);
+            }
+        }
+    }
+}
+
+impl Default for ClockFace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant8;
+
+    fn rows(framebuffer: &Framebuffer<Variant8, u8>) -> Vec<String> {
+        framebuffer
+            .values()
+            .chunks(18)
+            .map(|line| {
+                line.iter()
+                    .map(|&v| if v > 0 { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clock_face() {
+        let mut framebuffer = Framebuffer::<Variant8, u8>::new();
+        framebuffer.fill(1);
+
+        ClockFace::new()
+            .with_top_line(1)
+            .render(&mut framebuffer, 9, 41, 0, 0xff);
+        assert_eq!(
+            rows(&framebuffer),
+            vec![
+                "##################",
+                "###.###...#.#..#.#",
+                "#.#.#.#.#.#.#.##.#",
+                "#.#.###...###..#.#",
+                "#.#...#.#...#..#.#",
+                "###.###.....#.####",
+                "##################",
+                "##################",
+            ]
+        );
+
+        ClockFace::new()
+            .with_alignment(Alignment::Right)
+            .with_leading_zero(false)
+            .with_blinking_colon(true)
+            .render(&mut framebuffer, 7, 5, 1, 0xff);
+        assert_eq!(
+            rows(&framebuffer)[..5],
+            [
+                "#....###...###.###",
+                "#......#...#.#.#..",
+                "#......#...#.#.###",
+                "#......#...#.#...#",
+                "#......#...###.###",
+            ]
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Compositing a few grayscale layers (e.g. background, text and overlay) into a
+//! frame, resolved when the frame is written.
+
+use core::marker::PhantomData;
+
+use embedded_hal::{blocking::delay::DelayUs, digital::v2::OutputPin};
+
+use crate::{
+    interface::RegisterAccess,
+    vsync::{Vsync, VsyncError},
+    DeviceVariant, Error, Lp586x, PwmAccess, MAX_DOTS,
+};
+
+/// How a layer is combined with the layers below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// The brighter of the layer and the layers below
+    #[default]
+    Max,
+    /// Sum of the layer and the layers below, saturating at full scale
+    AddSaturating,
+    /// Dots of the layer that are not off replace the layers below, dots that are off
+    /// are transparent
+    Replace,
+}
+
+/// PWM value type of a [`Compositor`], `u8` or `u16` matching the data mode.
+pub trait LayerValue: Copy + Default + Ord {
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+impl LayerValue for u8 {
+    fn saturating_add(self, other: Self) -> Self {
+        u8::saturating_add(self, other)
+    }
+}
+
+impl LayerValue for u16 {
+    fn saturating_add(self, other: Self) -> Self {
+        u16::saturating_add(self, other)
+    }
+}
+
+impl BlendMode {
+    /// Combines `value` of a layer with `below`, the value of the layers below.
+    pub fn blend<T: LayerValue>(self, below: T, value: T) -> T {
+        match self {
+            BlendMode::Max => below.max(value),
+            BlendMode::AddSaturating => below.saturating_add(value),
+            BlendMode::Replace if value != T::default() => value,
+            BlendMode::Replace => below,
+        }
+    }
+}
+
+/// `N` layers of PWM values of type `T`, composited bottom (layer 0) to top.
+///
+/// Render into the layers independently with [`Compositor::layer_mut`] and write the
+/// composited frame with [`Compositor::flush`], so the application never merges
+/// buffers itself.
+pub struct Compositor<DV, T, const N: usize> {
+    layers: [[T; MAX_DOTS]; N],
+    blend_modes: [BlendMode; N],
+    visible: [bool; N],
+    _phantom_data: PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant, T: LayerValue, const N: usize> Compositor<DV, T, N> {
+    /// Creates `N` visible layers with all dots off, blended with [`BlendMode::Max`].
+    pub fn new() -> Self {
+        Self {
+            layers: [[T::default(); MAX_DOTS]; N],
+            blend_modes: [BlendMode::default(); N],
+            visible: [true; N],
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Sets the blend mode of `layer`.
+    pub fn with_blend_mode(mut self, layer: usize, blend_mode: BlendMode) -> Self {
+        self.set_blend_mode(layer, blend_mode);
+        self
+    }
+
+    /// Sets the blend mode of `layer`.
+    pub fn set_blend_mode(&mut self, layer: usize, blend_mode: BlendMode) {
+        self.blend_modes[layer] = blend_mode;
+    }
+
+    /// Shows or hides `layer`, hidden layers are skipped when compositing.
+    pub fn set_visible(&mut self, layer: usize, visible: bool) {
+        self.visible[layer] = visible;
+    }
+
+    /// PWM values of `layer`, indexed by dot.
+    pub fn layer(&self, layer: usize) -> &[T] {
+        &self.layers[layer][..DV::NUM_DOTS as usize]
+    }
+
+    /// Mutable PWM values of `layer`, indexed by dot.
+    pub fn layer_mut(&mut self, layer: usize) -> &mut [T] {
+        &mut self.layers[layer][..DV::NUM_DOTS as usize]
+    }
+
+    /// Composites the visible layers into `frame`, indexed by dot. Dots past the end of
+    /// `frame` are skipped.
+    pub fn composite(&self, frame: &mut [T]) {
+        frame.fill(T::default());
+
+        for layer in (0..N).filter(|&layer| self.visible[layer]) {
+            let blend_mode = self.blend_modes[layer];
+            frame
+                .iter_mut()
+                .zip(self.layer(layer))
+                .for_each(|(below, &value)| *below = blend_mode.blend(*below, value));
+        }
+    }
+
+    /// Composites the visible layers and writes the frame to `driver`.
+    pub fn flush<I, DM, IE>(&self, driver: &mut Lp586x<DV, I, DM>) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        let mut frame = [T::default(); MAX_DOTS];
+        let frame = &mut frame[..DV::NUM_DOTS as usize];
+        self.composite(frame);
+
+        driver.set_pwm(0, frame)
+    }
+
+    /// Composites the visible layers, writes the frame to `driver` and latches it with
+    /// a pulse on `vsync` (Mode 2 and Mode 3).
+    pub fn flush_with_vsync<I, DM, IE, P, D>(
+        &self,
+        driver: &mut Lp586x<DV, I, DM>,
+        vsync: &mut Vsync<P, D>,
+    ) -> Result<(), VsyncError<IE, P::Error>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+        P: OutputPin,
+        D: DelayUs<u32>,
+    {
+        self.flush(driver).map_err(VsyncError::Driver)?;
+
+        vsync.pulse().map_err(VsyncError::Pin)
+    }
+}
+
+impl<DV: DeviceVariant, T: LayerValue, const N: usize> Default for Compositor<DV, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_compositor() {
+        let mut compositor = Compositor::<Variant1, u8, 3>::new()
+            .with_blend_mode(1, BlendMode::AddSaturating)
+            .with_blend_mode(2, BlendMode::Replace);
+        compositor.layer_mut(0).fill(0x40);
+        compositor.layer_mut(1)[..3].copy_from_slice(&[0x10, 0xf0, 0]);
+        compositor.layer_mut(2)[2..4].copy_from_slice(&[0x01, 0x02]);
+
+        let mut expected = vec![0x40; 18];
+        expected[..4].copy_from_slice(&[0x50, 0xff, 0x01, 0x02]);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, expected),
+            Access::WriteRegisters(0x200, vec![0x02; 18]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        compositor.flush(&mut ledmatrix).unwrap();
+
+        compositor.set_visible(1, false);
+        compositor.layer_mut(0).fill(0x02);
+        compositor.set_blend_mode(2, BlendMode::Max);
+        compositor.flush(&mut ledmatrix).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
     register::BitFlags, CurrentSetting, DataRefMode, DeviceVariant, DownDeghost, LineBlankingTime,
-    PwmFrequency, PwmScaleMode, UpDeghost,
+    LowBrightnessCompensation, PwmFrequency, PwmScaleMode, UpDeghost,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Configuration {
     // dev_initial
     pub max_line_num: u8,
@@ -17,9 +17,9 @@ pub struct Configuration {
     pub cs_turn_on_delay: bool,
 
     // dev_config2
-    pub comp_group3: u8,
-    pub comp_group2: u8,
-    pub comp_group1: u8,
+    pub comp_group3: LowBrightnessCompensation,
+    pub comp_group2: LowBrightnessCompensation,
+    pub comp_group1: LowBrightnessCompensation,
     pub lod_removal: bool,
     pub lsd_removal: bool,
 
@@ -42,9 +42,9 @@ impl Configuration {
             pwm_phase_shift: false,
             cs_turn_on_delay: false,
 
-            comp_group1: 0,
-            comp_group2: 0,
-            comp_group3: 0,
+            comp_group1: LowBrightnessCompensation::Off,
+            comp_group2: LowBrightnessCompensation::Off,
+            comp_group3: LowBrightnessCompensation::Off,
             lod_removal: false,
             lsd_removal: false,
 
@@ -55,7 +55,193 @@ impl Configuration {
         }
     }
 
-    // TODO: implement builder pattern
+    /// Starting point for battery powered designs: lower PWM frequency to reduce
+    /// switching losses, reduced maximum current and staggered current sink turn-on.
+    pub fn preset_low_power<DV: DeviceVariant>() -> Self {
+        Self::new::<DV>()
+            .with_pwm_frequency(PwmFrequency::Pwm62_5kHz)
+            .with_maximum_current(CurrentSetting::Max5mA)
+            .with_cs_turn_on_delay(true)
+    }
+
+    /// Starting point for fast refresh: 125 kHz PWM, short line switch blanking time
+    /// and stronger downside deghosting to compensate for the shorter blanking.
+    pub fn preset_high_refresh<DV: DeviceVariant>() -> Self {
+        Self::new::<DV>()
+            .with_pwm_frequency(PwmFrequency::Pwm125kHz)
+            .with_switch_blanking_time(LineBlankingTime::Blank0_5us)
+            .with_down_deghost(DownDeghost::Medium)
+    }
+
+    /// Starting point for displays filmed by cameras: 125 kHz PWM and PWM phase shift,
+    /// which spreads the current sink on-times and reduces banding in video.
+    pub fn preset_camera_friendly<DV: DeviceVariant>() -> Self {
+        Self::new::<DV>().adjusted_for_camera()
+    }
+
+    /// Applies the camera friendly settings of [`Configuration::preset_camera_friendly`]
+    /// on top of this configuration.
+    pub fn adjusted_for_camera(self) -> Self {
+        self.with_pwm_frequency(PwmFrequency::Pwm125kHz)
+            .with_pwm_phase_shift(true)
+    }
+
+    /// Approximate rate in Hz at which all scan lines are refreshed once.
+    pub fn scan_refresh_hz(&self) -> u32 {
+        let pwm_frequency_hz = match self.pwm_frequency {
+            PwmFrequency::Pwm125kHz => 125_000,
+            PwmFrequency::Pwm62_5kHz => 62_500,
+        };
+
+        pwm_frequency_hz / self.max_line_num.max(1) as u32
+    }
+
+    pub fn with_max_line_num(mut self, max_line_num: u8) -> Self {
+        self.max_line_num = max_line_num;
+        self
+    }
+
+    pub fn with_data_ref_mode(mut self, data_ref_mode: DataRefMode) -> Self {
+        self.data_ref_mode = data_ref_mode;
+        self
+    }
+
+    pub fn with_pwm_frequency(mut self, pwm_frequency: PwmFrequency) -> Self {
+        self.pwm_frequency = pwm_frequency;
+        self
+    }
+
+    pub fn with_switch_blanking_time(mut self, switch_blanking_time: LineBlankingTime) -> Self {
+        self.switch_blanking_time = switch_blanking_time;
+        self
+    }
+
+    pub fn with_pwm_scale_mode(mut self, pwm_scale_mode: PwmScaleMode) -> Self {
+        self.pwm_scale_mode = pwm_scale_mode;
+        self
+    }
+
+    pub fn with_pwm_phase_shift(mut self, pwm_phase_shift: bool) -> Self {
+        self.pwm_phase_shift = pwm_phase_shift;
+        self
+    }
+
+    pub fn with_cs_turn_on_delay(mut self, cs_turn_on_delay: bool) -> Self {
+        self.cs_turn_on_delay = cs_turn_on_delay;
+        self
+    }
+
+    pub fn with_comp_group1(mut self, comp_group1: LowBrightnessCompensation) -> Self {
+        self.comp_group1 = comp_group1;
+        self
+    }
+
+    pub fn with_comp_group2(mut self, comp_group2: LowBrightnessCompensation) -> Self {
+        self.comp_group2 = comp_group2;
+        self
+    }
+
+    pub fn with_comp_group3(mut self, comp_group3: LowBrightnessCompensation) -> Self {
+        self.comp_group3 = comp_group3;
+        self
+    }
+
+    pub fn with_lod_removal(mut self, lod_removal: bool) -> Self {
+        self.lod_removal = lod_removal;
+        self
+    }
+
+    pub fn with_lsd_removal(mut self, lsd_removal: bool) -> Self {
+        self.lsd_removal = lsd_removal;
+        self
+    }
+
+    pub fn with_down_deghost(mut self, down_deghost: DownDeghost) -> Self {
+        self.down_deghost = down_deghost;
+        self
+    }
+
+    pub fn with_up_deghost(mut self, up_deghost: UpDeghost) -> Self {
+        self.up_deghost = up_deghost;
+        self
+    }
+
+    pub fn with_maximum_current(mut self, maximum_current: CurrentSetting) -> Self {
+        self.maximum_current = maximum_current;
+        self
+    }
+
+    pub fn with_up_deghost_enable(mut self, up_deghost_enable: bool) -> Self {
+        self.up_deghost_enable = up_deghost_enable;
+        self
+    }
+
+    /// Values of the `DEV_INITIAL`, `DEV_CONFIG1`, `DEV_CONFIG2` and `DEV_CONFIG3`
+    /// registers, in register order.
+    pub fn register_values(&self) -> [u8; 4] {
+        [
+            self.dev_initial_reg_value(),
+            self.dev_config1_reg_value(),
+            self.dev_config2_reg_value(),
+            self.dev_config3_reg_value(),
+        ]
+    }
+
+    /// Decodes the values of the `DEV_INITIAL`, `DEV_CONFIG1`, `DEV_CONFIG2` and
+    /// `DEV_CONFIG3` registers, e.g. read back from the chip. Inverse of
+    /// [`Configuration::register_values`].
+    pub fn from_register_values(values: [u8; 4]) -> Self {
+        let [dev_initial, dev_config1, dev_config2, dev_config3] = values;
+
+        Self {
+            max_line_num: (dev_initial >> BitFlags::DEV_INITIAL_MAX_LINE_NUM_SHIFT)
+                & BitFlags::DEV_INITIAL_MAX_LINE_NUM_MASK,
+            data_ref_mode: DataRefMode::from_register_value(
+                dev_initial >> BitFlags::DEV_INITIAL_DATA_REF_MODE_SHIFT,
+            ),
+            pwm_frequency: if dev_initial & BitFlags::DEV_INITIAL_PWM_FREQ != 0 {
+                PwmFrequency::Pwm125kHz
+            } else {
+                PwmFrequency::Pwm62_5kHz
+            },
+
+            switch_blanking_time: if dev_config1 & BitFlags::DEV_CONFIG1_SW_BLK != 0 {
+                LineBlankingTime::Blank0_5us
+            } else {
+                LineBlankingTime::Blank1us
+            },
+            pwm_scale_mode: if dev_config1 & BitFlags::DEV_CONFIG1_PWM_SCALE_MODE != 0 {
+                PwmScaleMode::Exponential
+            } else {
+                PwmScaleMode::Linear
+            },
+            pwm_phase_shift: dev_config1 & BitFlags::DEV_CONFIG1_PWM_PHASE_SHIFT != 0,
+            cs_turn_on_delay: dev_config1 & BitFlags::DEV_CONFIG1_CS_ON_SHIFT != 0,
+
+            comp_group3: LowBrightnessCompensation::from_register_value(
+                dev_config2 >> BitFlags::DEV_CONFIG2_COMP_GROUP3_SHIFT,
+            ),
+            comp_group2: LowBrightnessCompensation::from_register_value(
+                dev_config2 >> BitFlags::DEV_CONFIG2_COMP_GROUP2_SHIFT,
+            ),
+            comp_group1: LowBrightnessCompensation::from_register_value(
+                dev_config2 >> BitFlags::DEV_CONFIG2_COMP_GROUP1_SHIFT,
+            ),
+            lod_removal: dev_config2 & BitFlags::DEV_CONFIG2_LOD_REMOVAL != 0,
+            lsd_removal: dev_config2 & BitFlags::DEV_CONFIG2_LSD_REMOVAL != 0,
+
+            down_deghost: DownDeghost::from_register_value(
+                dev_config3 >> BitFlags::DEV_CONFIG3_DOWN_DEGHOST_SHIFT,
+            ),
+            up_deghost: UpDeghost::from_register_value(
+                dev_config3 >> BitFlags::DEV_CONFIG3_UP_DEGHOST_SHIFT,
+            ),
+            maximum_current: CurrentSetting::from_register_value(
+                dev_config3 >> BitFlags::DEV_CONFIG3_MAXIMUM_CURRENT_SHIFT,
+            ),
+            up_deghost_enable: dev_config3 & BitFlags::DEV_CONFIG3_UP_DEGHOST_ENABLE != 0,
+        }
+    }
 
     pub fn dev_initial_reg_value(&self) -> u8 {
         // wtf is going on here? when I remove the return [...]; there are loads
@@ -95,9 +281,9 @@ impl Configuration {
                 .lod_removal
                 .then_some(BitFlags::DEV_CONFIG2_LOD_REMOVAL)
                 .unwrap_or(0)
-            | self.comp_group1.clamp(0, 3) << BitFlags::DEV_CONFIG2_COMP_GROUP1_SHIFT
-            | self.comp_group2.clamp(0, 3) << BitFlags::DEV_CONFIG2_COMP_GROUP2_SHIFT
-            | self.comp_group3.clamp(0, 3) << BitFlags::DEV_CONFIG2_COMP_GROUP3_SHIFT
+            | self.comp_group1.register_value() << BitFlags::DEV_CONFIG2_COMP_GROUP1_SHIFT
+            | self.comp_group2.register_value() << BitFlags::DEV_CONFIG2_COMP_GROUP2_SHIFT
+            | self.comp_group3.register_value() << BitFlags::DEV_CONFIG2_COMP_GROUP3_SHIFT
     }
 
     pub fn dev_config3_reg_value(&self) -> u8 {
@@ -109,3 +295,53 @@ impl Configuration {
             | self.down_deghost.register_value() << BitFlags::DEV_CONFIG3_DOWN_DEGHOST_SHIFT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant8;
+
+    #[test]
+    fn test_presets() {
+        let low_power = Configuration::preset_low_power::<Variant8>();
+        assert_eq!(low_power.dev_initial_reg_value(), 0b0100_0100);
+        assert_eq!(low_power.dev_config1_reg_value(), 0b0000_0001);
+        assert_eq!(low_power.dev_config3_reg_value(), 0b0101_0011);
+
+        let high_refresh = Configuration::preset_high_refresh::<Variant8>();
+        assert_eq!(high_refresh.dev_initial_reg_value(), 0b0100_0101);
+        assert_eq!(high_refresh.dev_config1_reg_value(), 0b0000_1000);
+        assert_eq!(high_refresh.dev_config3_reg_value(), 0b1001_0111);
+
+        let camera_friendly =
+            Configuration::preset_camera_friendly::<Variant8>().with_up_deghost_enable(false);
+        assert_eq!(camera_friendly.dev_initial_reg_value(), 0b0100_0101);
+        assert_eq!(camera_friendly.dev_config1_reg_value(), 0b0000_0010);
+        assert_eq!(camera_friendly.dev_config3_reg_value(), 0b0101_0110);
+        assert_eq!(camera_friendly.scan_refresh_hz(), 15_625);
+
+        assert_ne!(low_power, high_refresh);
+        assert_eq!(
+            Configuration::preset_camera_friendly::<Variant8>(),
+            Configuration::new::<Variant8>().adjusted_for_camera()
+        );
+    }
+
+    #[test]
+    fn test_register_values() {
+        let configuration = Configuration::new::<Variant8>()
+            .with_data_ref_mode(DataRefMode::Mode2)
+            .with_pwm_scale_mode(PwmScaleMode::Exponential)
+            .with_comp_group1(LowBrightnessCompensation::Mode1)
+            .with_comp_group2(LowBrightnessCompensation::Mode2)
+            .with_comp_group3(LowBrightnessCompensation::Mode3)
+            .with_lod_removal(true)
+            .with_up_deghost(UpDeghost::Gnd)
+            .with_maximum_current(CurrentSetting::Max50mA);
+
+        assert_eq!(
+            configuration.register_values(),
+            [0b0100_0010, 0b0000_0100, 0b1110_0110, 0b0111_1111]
+        );
+    }
+}
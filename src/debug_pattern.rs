@@ -0,0 +1,93 @@
+//! Test patterns for verifying the wiring of a matrix during board bring-up.
+
+use crate::{
+    interface::RegisterAccess, DataMode16Bit, DataMode8Bit, DeviceVariant, Error, Lp586x,
+    PwmAccess, MAX_DOTS,
+};
+
+/// PWM value of `dot` in the coordinate pattern.
+///
+/// Brightness rises with the current sink index, so CS0 is the dimmest and CS17 the
+/// brightest dot of each line. All dots of `marked_line` are at full brightness.
+pub fn coordinate_pattern_value<DV: DeviceVariant>(dot: u16, marked_line: Option<u8>) -> u8 {
+    let line = dot / DV::NUM_CURRENT_SINKS as u16;
+    let cs = dot % DV::NUM_CURRENT_SINKS as u16;
+
+    if marked_line.map(u16::from) == Some(line) {
+        0xff
+    } else {
+        ((cs + 1) * 0xff / DV::NUM_CURRENT_SINKS as u16) as u8
+    }
+}
+
+impl<DV: DeviceVariant, I, IE> Lp586x<DV, I, DataMode8Bit>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Shows the coordinate pattern (see [`coordinate_pattern_value`]) on all dots.
+    /// Alternate `marked_line` between `Some(line)` and `None` to blink a line marker.
+    pub fn show_coordinate_pattern(&mut self, marked_line: Option<u8>) -> Result<(), Error<IE>> {
+        let mut buffer = [0u8; MAX_DOTS];
+
+        buffer[..DV::NUM_DOTS as usize]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(dot, value)| {
+                *value = coordinate_pattern_value::<DV>(dot as u16, marked_line)
+            });
+
+        self.set_pwm(0, &buffer[..DV::NUM_DOTS as usize])
+    }
+}
+
+impl<DV: DeviceVariant, I, IE> Lp586x<DV, I, DataMode16Bit>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Shows the coordinate pattern (see [`coordinate_pattern_value`]) on all dots.
+    /// Alternate `marked_line` between `Some(line)` and `None` to blink a line marker.
+    pub fn show_coordinate_pattern(&mut self, marked_line: Option<u8>) -> Result<(), Error<IE>> {
+        let mut buffer = [0u16; MAX_DOTS];
+
+        buffer[..DV::NUM_DOTS as usize]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(dot, value)| {
+                *value = coordinate_pattern_value::<DV>(dot as u16, marked_line) as u16 * 0x101
+            });
+
+        self.set_pwm(0, &buffer[..DV::NUM_DOTS as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5862,
+    };
+
+    #[test]
+    fn test_show_coordinate_pattern() {
+        let line: Vec<u8> = (1..=18u16).map(|cs| (cs * 0xff / 18) as u8).collect();
+        let mut frame = line.clone();
+        frame.extend([0xff; 18]);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, frame),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        ledmatrix.show_coordinate_pattern(Some(1)).unwrap();
+
+        assert_eq!(line[0], 14);
+        assert_eq!(line[17], 0xff);
+
+        ledmatrix.release().done();
+    }
+}
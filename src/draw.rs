@@ -0,0 +1,148 @@
+//! Fast paths for drawing horizontal and vertical lines, e.g. for bar charts and
+//! borders.
+//!
+//! Coordinates are `x` for the current sink and `y` for the line. Lines are clipped to
+//! the device variant.
+
+use crate::{
+    interface::RegisterAccess, DeviceVariant, Dot, Error, Lp586x, Op, PwmAccess, PwmDataMode,
+    MAX_DOTS,
+};
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: PwmDataMode,
+{
+    /// Sets `len` dots of line `y` starting at current sink `x` to `value`, in a single
+    /// write.
+    pub fn draw_hline<T: Copy>(&mut self, x: u8, y: u8, len: u8, value: T) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let Some(start) = Dot::<DV>::from_line_cs(y, x) else {
+            return Ok(());
+        };
+        let len = len.min(DV::NUM_CURRENT_SINKS - x) as usize;
+        if len == 0 {
+            return Ok(());
+        }
+
+        self.set_pwm(start.index(), &[value; MAX_DOTS][..len])
+    }
+
+    /// Sets `len` dots of current sink `x` starting at line `y` to `value`. The PWM
+    /// registers spanning the line are read and written back with the dots of the line
+    /// changed, so the line costs a single read and write instead of one write per dot.
+    pub fn draw_vline<T: Copy + Into<u16>>(
+        &mut self,
+        x: u8,
+        y: u8,
+        len: u8,
+        value: T,
+    ) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let last_line = y.saturating_add(len).min(DV::NUM_LINES).saturating_sub(1);
+        let (Some(first), Some(last)) = (
+            Dot::<DV>::from_line_cs(y, x),
+            Dot::<DV>::from_line_cs(last_line, x),
+        ) else {
+            return Ok(());
+        };
+        if len == 0 {
+            return Ok(());
+        }
+
+        let registers_per_dot = DM::REGISTERS_PER_DOT as usize;
+        let dots = (last.index() - first.index()) as usize + 1;
+        let register = Self::pwm_register_for(first.index());
+
+        let mut buffer = [0u8; MAX_DOTS * 2];
+        let span = &mut buffer[..dots * registers_per_dot];
+        self.interface
+            .read_registers(register, span)
+            .map_err(|e| e.tagged(Op::ReadFrame))?;
+
+        let value_bytes = self.apply_low_pwm_policy(value.into()).to_le_bytes();
+        span.chunks_mut(registers_per_dot)
+            .step_by(DV::NUM_CURRENT_SINKS as usize)
+            .for_each(|dot| dot.copy_from_slice(&value_bytes[..registers_per_dot]));
+
+        self.write_pwm_registers(first.index(), dots, register, span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        LowPwmPolicy, Lp5862,
+    };
+
+    #[test]
+    fn test_draw_hline() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 18 + 16, vec![7, 7]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        ledmatrix.draw_hline(16, 1, 5, 7u8).unwrap();
+        ledmatrix.draw_hline(18, 1, 5, 7u8).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_draw_vline() {
+        let mut span = vec![0x11; 2 * 19];
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x200 + 3 * 2, span.clone()),
+            Access::WriteRegisters(0x200 + 3 * 2, {
+                span[..2].copy_from_slice(&[0x34, 0x12]);
+                span[36..].copy_from_slice(&[0x34, 0x12]);
+                span
+            }),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+        ledmatrix.draw_vline(3, 0, 4, 0x1234u16).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_draw_vline_low_pwm_policy() {
+        let mut span = vec![0; 2 * 19];
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x200, span.clone()),
+            Access::WriteRegisters(0x200, {
+                span[..2].copy_from_slice(&6400u16.to_le_bytes());
+                span[36..].copy_from_slice(&6400u16.to_le_bytes());
+                span
+            }),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap()
+            .with_low_pwm_policy(LowPwmPolicy::Clamp);
+        ledmatrix.draw_vline(0, 0, 2, 1u16).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
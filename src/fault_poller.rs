@@ -0,0 +1,224 @@
+//! Periodic fault monitoring with a bounded amount of bus traffic per tick.
+
+use crate::{
+    interface::RegisterAccess,
+    register::{BitFlags, Register},
+    DataModeMarker, DeviceVariant, Dot, Error, FaultKind, GlobalFaultState, Lp586x, Op, MAX_LINES,
+};
+
+/// Change in fault state reported by [`FaultPoller::tick`].
+pub enum FaultChange<DV> {
+    /// The global fault state register changed
+    Global(GlobalFaultState),
+    /// A dot started (`true`) or stopped (`false`) reporting a fault of the given kind
+    Dot(Dot<DV>, FaultKind, bool),
+}
+
+/// Fault monitor to be ticked periodically from the application.
+///
+/// Every `check_interval` ticks the global fault state is read. If it reports a fault,
+/// or faults were known before, the per dot states are scanned one line per tick.
+/// Only changes are reported, so a permanently dead LED is reported once.
+pub struct FaultPoller<DV> {
+    check_interval: u16,
+    ticks: u16,
+    fault_state: u8,
+    scan_line: Option<u8>,
+    open: [[u8; 3]; MAX_LINES],
+    short: [[u8; 3]; MAX_LINES],
+    _phantom_data: core::marker::PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant> FaultPoller<DV> {
+    /// Create a poller checking the global fault state every `check_interval` ticks.
+    pub fn new(check_interval: u16) -> Self {
+        Self {
+            check_interval: check_interval.max(1),
+            ticks: 0,
+            fault_state: 0,
+            scan_line: None,
+            open: [[0; 3]; MAX_LINES],
+            short: [[0; 3]; MAX_LINES],
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    /// True, if any dot is currently known to be faulty.
+    pub fn has_faults(&self) -> bool {
+        self.open
+            .iter()
+            .chain(&self.short)
+            .flatten()
+            .any(|&v| v != 0)
+    }
+
+    /// Does the work due for this tick on `driver`: at most one register read of the
+    /// global fault state, or the open and short states of a single line.
+    pub fn tick<I, DM, IE>(
+        &mut self,
+        driver: &mut Lp586x<DV, I, DM>,
+        mut on_change: impl FnMut(FaultChange<DV>),
+    ) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        if let Some(line) = self.scan_line {
+            self.scan_line = (line + 1 < DV::NUM_LINES).then_some(line + 1);
+            return self.scan(driver, line, &mut on_change);
+        }
+
+        self.ticks += 1;
+        if self.ticks < self.check_interval {
+            return Ok(());
+        }
+        self.ticks = 0;
+
+        let fault_state = driver
+            .interface
+            .read_register(Register::FAULT_STATE)
+            .map_err(|e| e.tagged(Op::Faults))?;
+        let any_fault =
+            fault_state & (BitFlags::FAULT_STATE_GLOBAL_LOD | BitFlags::FAULT_STATE_GLOBAL_LSD);
+        if fault_state != self.fault_state {
+            self.fault_state = fault_state;
+            on_change(FaultChange::Global(GlobalFaultState::from_reg_value(
+                fault_state,
+            )));
+        }
+
+        if any_fault != 0 || self.has_faults() {
+            self.scan_line = Some(0);
+        }
+
+        Ok(())
+    }
+
+    fn scan<I, DM, IE>(
+        &mut self,
+        driver: &mut Lp586x<DV, I, DM>,
+        line: u8,
+        on_change: &mut impl FnMut(FaultChange<DV>),
+    ) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        for (start_register, kind) in [
+            (Register::DOT_LOD_START, FaultKind::Open),
+            (Register::DOT_LSD_START, FaultKind::Short),
+        ] {
+            let mut states = [0u8; 3];
+            driver
+                .interface
+                .read_registers(start_register + line as u16 * 3, &mut states)
+                .map_err(|e| e.tagged(Op::Faults))?;
+            // ignore the bits of current sinks the variant doesn't have
+            states.iter_mut().enumerate().for_each(|(i, state)| {
+                let sinks = DV::NUM_CURRENT_SINKS.saturating_sub(i as u8 * 8).min(8);
+                *state &= ((1u16 << sinks) - 1) as u8;
+            });
+
+            let known = match kind {
+                FaultKind::Open => &mut self.open[line as usize],
+                FaultKind::Short => &mut self.short[line as usize],
+            };
+
+            (0..DV::NUM_CURRENT_SINKS)
+                .map(|cs| (cs, states[(cs / 8) as usize] & (1 << (cs % 8))))
+                .filter(|&(cs, state)| known[(cs / 8) as usize] & (1 << (cs % 8)) != state)
+                .filter_map(|(cs, state)| Some((Dot::from_line_cs(line, cs)?, state != 0)))
+                .for_each(|(dot, faulty)| on_change(FaultChange::Dot(dot, kind, faulty)));
+
+            *known = states;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5862, Variant2,
+    };
+
+    #[test]
+    fn test_fault_poller() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            // tick 2: global check reports an open LED
+            Access::ReadRegister(0x064, 0b10),
+            // tick 3 and 4: line scans
+            Access::ReadRegisters(0x065, vec![0, 0b100, 0]),
+            Access::ReadRegisters(0x086, vec![0, 0, 0]),
+            Access::ReadRegisters(0x065 + 3, vec![0, 0, 0]),
+            Access::ReadRegisters(0x086 + 3, vec![0, 0, 0]),
+            // tick 6: unchanged global state, faults known, so scan again
+            Access::ReadRegister(0x064, 0b10),
+            Access::ReadRegisters(0x065, vec![0, 0b100, 0]),
+            Access::ReadRegisters(0x086, vec![0, 0, 0]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        let mut poller = FaultPoller::<Variant2>::new(2);
+
+        let mut changes = Vec::new();
+        for _ in 0..7 {
+            poller
+                .tick(&mut ledmatrix, |change| {
+                    changes.push(match change {
+                        FaultChange::Global(state) => (None, state.led_open_detected()),
+                        FaultChange::Dot(dot, kind, faulty) => {
+                            assert_eq!(kind, FaultKind::Open);
+                            (Some(dot.index()), faulty)
+                        }
+                    })
+                })
+                .unwrap();
+        }
+
+        assert_eq!(changes, vec![(None, true), (Some(10), true)]);
+        assert!(poller.has_faults());
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_fault_poller_ignores_missing_sinks() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x064, 0b10),
+            // bits above CS17 set
+            Access::ReadRegisters(0x065, vec![0, 0, 0b1111_1100]),
+            Access::ReadRegisters(0x086, vec![0, 0, 0b1000_0000]),
+            Access::ReadRegisters(0x065 + 3, vec![0, 0, 0]),
+            Access::ReadRegisters(0x086 + 3, vec![0, 0, 0]),
+            // no faults known, so no further scan
+            Access::ReadRegister(0x064, 0),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        let mut poller = FaultPoller::<Variant2>::new(1);
+
+        let mut dot_changes = 0;
+        for _ in 0..4 {
+            poller
+                .tick(&mut ledmatrix, |change| {
+                    if let FaultChange::Dot(..) = change {
+                        dot_changes += 1;
+                    }
+                })
+                .unwrap();
+        }
+
+        assert_eq!(dot_changes, 0);
+        assert!(!poller.has_faults());
+
+        ledmatrix.release().done();
+    }
+}
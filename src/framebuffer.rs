@@ -0,0 +1,216 @@
+//! Frame of PWM values kept in MCU RAM, written to the chip on demand.
+
+use core::{marker::PhantomData, ops::Range};
+
+use crate::{
+    image::Sprite, interface::RegisterAccess, DeviceVariant, Dot, Error, Lp586x, PwmAccess,
+    MAX_DOTS,
+};
+
+/// Full frame of PWM values of type `T` (`u8` or `u16`, matching the data mode), with
+/// tracking of the changed (dirty) dots.
+///
+/// Setting single dots only changes RAM. [`Framebuffer::flush`] writes all dots from
+/// the first to the last changed one in a single burst, so drawing an animation frame
+/// dot by dot costs one bus transaction per frame instead of one per dot.
+#[derive(Debug, Clone)]
+pub struct Framebuffer<DV, T> {
+    values: [T; MAX_DOTS],
+    dirty: Option<Range<u16>>,
+    _phantom_data: PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant, T: Copy + Default + PartialEq> Framebuffer<DV, T> {
+    /// Creates a frame with all dots off. All dots are dirty, so the first flush
+    /// writes the whole frame.
+    pub fn new() -> Self {
+        Self {
+            values: [T::default(); MAX_DOTS],
+            dirty: Some(0..DV::NUM_DOTS),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// PWM values of all dots, indexed by dot.
+    pub fn values(&self) -> &[T] {
+        &self.values[..DV::NUM_DOTS as usize]
+    }
+
+    /// PWM value of `dot`, `None` if the dot is out of range.
+    pub fn get(&self, dot: u16) -> Option<T> {
+        self.values().get(dot as usize).copied()
+    }
+
+    /// Sets the PWM value of `dot`. Dots out of range are ignored.
+    pub fn set(&mut self, dot: u16, value: T) {
+        let Some(current) = self.values[..DV::NUM_DOTS as usize].get_mut(dot as usize) else {
+            return;
+        };
+
+        if *current != value {
+            *current = value;
+            self.mark_dirty(dot..dot + 1);
+        }
+    }
+
+    /// Sets the PWM value of the dot at `line` and current sink `cs`. Dots out of range
+    /// are ignored.
+    pub fn set_at(&mut self, line: u8, cs: u8, value: T) {
+        if let Some(dot) = Dot::<DV>::from_line_cs(line, cs) {
+            self.set(dot.index(), value);
+        }
+    }
+
+    /// Sets all dots to `value`.
+    pub fn fill(&mut self, value: T) {
+        (0..DV::NUM_DOTS).for_each(|dot| self.set(dot, value));
+    }
+
+    /// Copies `values` into the frame, starting at dot `start`. Values past the last
+    /// dot are ignored.
+    pub fn copy_from(&mut self, start: u16, values: &[T]) {
+        (start..DV::NUM_DOTS)
+            .zip(values)
+            .for_each(|(dot, &value)| self.set(dot, value));
+    }
+
+    /// Draws `sprite` with its top left pixel at current sink `x` and line `y`, mapping
+    /// pixel values with `map`. Transparent pixels and pixels outside the frame are
+    /// skipped, so sprites can move partly off the edges.
+    fn draw_sprite_with(&mut self, x: i16, y: i16, sprite: &Sprite, map: impl Fn(u8) -> T) {
+        for row in 0..sprite.height() {
+            for column in 0..sprite.width() {
+                let (Ok(cs), Ok(line)) = (
+                    u8::try_from(x.saturating_add(column as i16)),
+                    u8::try_from(y.saturating_add(row as i16)),
+                ) else {
+                    continue;
+                };
+
+                if let Some(value) = sprite.pixel(column, row) {
+                    self.set_at(line, cs, map(value));
+                }
+            }
+        }
+    }
+
+    /// Marks `dots` as dirty, e.g. all dots after the chip was reset.
+    pub fn mark_dirty(&mut self, dots: Range<u16>) {
+        let dots = dots.start..dots.end.min(DV::NUM_DOTS);
+        if dots.is_empty() {
+            return;
+        }
+
+        self.dirty = Some(match self.dirty.take() {
+            Some(dirty) => dirty.start.min(dots.start)..dirty.end.max(dots.end),
+            None => dots,
+        });
+    }
+
+    /// Range of dots written by the next flush, `None` if nothing changed.
+    pub fn dirty_range(&self) -> Option<Range<u16>> {
+        self.dirty.clone()
+    }
+
+    /// Writes the dirty range to `driver` in a single write. The frame is clean
+    /// afterwards, unless the write fails.
+    pub fn flush<I, DM, IE>(&mut self, driver: &mut Lp586x<DV, I, DM>) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        let Some(dirty) = self.dirty.clone() else {
+            return Ok(());
+        };
+
+        driver.set_pwm(
+            dirty.start,
+            &self.values[dirty.start as usize..dirty.end as usize],
+        )?;
+        self.dirty = None;
+
+        Ok(())
+    }
+}
+
+impl<DV: DeviceVariant> Framebuffer<DV, u8> {
+    /// Draws `sprite` with its top left pixel at current sink `x` and line `y`.
+    /// Transparent pixels and pixels outside the frame are skipped.
+    pub fn draw_sprite(&mut self, x: i16, y: i16, sprite: &Sprite) {
+        self.draw_sprite_with(x, y, sprite, |value| value);
+    }
+}
+
+impl<DV: DeviceVariant> Framebuffer<DV, u16> {
+    /// Draws `sprite` with its top left pixel at current sink `x` and line `y`, with
+    /// pixel values expanded to full scale 16 bit. Transparent pixels and pixels
+    /// outside the frame are skipped.
+    pub fn draw_sprite(&mut self, x: i16, y: i16, sprite: &Sprite) {
+        self.draw_sprite_with(x, y, sprite, |value| value as u16 * 257);
+    }
+}
+
+impl<DV: DeviceVariant, T: Copy + Default + PartialEq> Default for Framebuffer<DV, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5862, Variant2,
+    };
+
+    #[test]
+    fn test_framebuffer() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![0; 36]),
+            Access::WriteRegisters(
+                0x200 + 3,
+                vec![7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9],
+            ),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let mut framebuffer = Framebuffer::<Variant2, u8>::new();
+        framebuffer.flush(&mut ledmatrix).unwrap();
+        assert_eq!(framebuffer.dirty_range(), None);
+
+        framebuffer.set(1, 0);
+        framebuffer.set(36, 5);
+        assert_eq!(framebuffer.dirty_range(), None);
+
+        framebuffer.set_at(1, 1, 9);
+        framebuffer.set(3, 7);
+        assert_eq!(framebuffer.dirty_range(), Some(3..20));
+        assert_eq!(framebuffer.get(19), Some(9));
+        framebuffer.flush(&mut ledmatrix).unwrap();
+        framebuffer.flush(&mut ledmatrix).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_draw_sprite() {
+        let sprite = Sprite::new(&[1, 0, 2, 3, 4, 5], 3).with_key(0);
+
+        let mut framebuffer = Framebuffer::<Variant2, u16>::new();
+        framebuffer.draw_sprite(16, -1, &sprite);
+        assert_eq!(framebuffer.values()[16..18], [0x0303, 0x0404]);
+        assert_eq!(framebuffer.values()[..16], [0; 16]);
+
+        let mut framebuffer = Framebuffer::<Variant2, u8>::new();
+        framebuffer.fill(9);
+        framebuffer.draw_sprite(-1, 0, &sprite);
+        assert_eq!(framebuffer.values()[..3], [9, 2, 9]);
+        assert_eq!(framebuffer.values()[18..21], [4, 5, 9]);
+    }
+}
@@ -0,0 +1,226 @@
+//! One large canvas spread over a grid of controllers, e.g. for multi-panel walls.
+
+use crate::{
+    framebuffer::Framebuffer, interface::RegisterAccess, DeviceVariant, Dot, Error, Lp586x,
+    PwmAccess,
+};
+
+/// Mounting of a tile within a [`DisplayGrid`].
+///
+/// Tiles keep their native size, current sinks along x and lines along y, so panels
+/// mounted upside down are described by flipping both axes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TileOrientation {
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl TileOrientation {
+    /// Tile mounted upright, current sink 0 left and line 0 on top.
+    pub const fn new() -> Self {
+        Self {
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    /// Tile mounted upside down.
+    pub const fn rotated_180() -> Self {
+        Self::new().with_flip_x(true).with_flip_y(true)
+    }
+
+    /// Mirror the current sinks, current sink 0 is on the right.
+    pub const fn with_flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    /// Mirror the lines, line 0 is at the bottom.
+    pub const fn with_flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+}
+
+/// Canvas of `COLS` x `ROWS` tiles, one [`Framebuffer`] per controller.
+///
+/// Pixel `x`, `y` of the canvas is mapped to its tile and to the line and current sink
+/// on it, honoring the [`TileOrientation`] of the tile. [`DisplayGrid::flush`] writes
+/// the changed part of every tile to its driver.
+#[derive(Debug, Clone)]
+pub struct DisplayGrid<DV, T, const COLS: usize, const ROWS: usize> {
+    tiles: [[Framebuffer<DV, T>; COLS]; ROWS],
+    orientations: [[TileOrientation; COLS]; ROWS],
+}
+
+impl<DV, T, const COLS: usize, const ROWS: usize> DisplayGrid<DV, T, COLS, ROWS>
+where
+    DV: DeviceVariant,
+    T: Copy + Default + PartialEq,
+{
+    /// Creates a canvas with all dots off and all tiles upright.
+    pub fn new() -> Self {
+        Self {
+            tiles: core::array::from_fn(|_| core::array::from_fn(|_| Framebuffer::new())),
+            orientations: [[TileOrientation::new(); COLS]; ROWS],
+        }
+    }
+
+    /// Sets the orientation of the tile at `col`, `row`.
+    pub fn with_orientation(
+        mut self,
+        col: usize,
+        row: usize,
+        orientation: TileOrientation,
+    ) -> Self {
+        self.set_orientation(col, row, orientation);
+        self
+    }
+
+    /// Sets the orientation of the tile at `col`, `row`. Tiles out of range are ignored.
+    pub fn set_orientation(&mut self, col: usize, row: usize, orientation: TileOrientation) {
+        if let Some(current) = self.orientations.get_mut(row).and_then(|r| r.get_mut(col)) {
+            *current = orientation;
+        }
+    }
+
+    /// Width of the canvas in pixels.
+    pub fn width(&self) -> u16 {
+        COLS as u16 * DV::NUM_CURRENT_SINKS as u16
+    }
+
+    /// Height of the canvas in pixels.
+    pub fn height(&self) -> u16 {
+        ROWS as u16 * DV::NUM_LINES as u16
+    }
+
+    /// Tile at `col`, `row`, e.g. to inspect the dots of a single controller.
+    pub fn tile(&self, col: usize, row: usize) -> &Framebuffer<DV, T> {
+        &self.tiles[row][col]
+    }
+
+    /// Mutable tile at `col`, `row`, e.g. to mark it dirty after a controller reset.
+    pub fn tile_mut(&mut self, col: usize, row: usize) -> &mut Framebuffer<DV, T> {
+        &mut self.tiles[row][col]
+    }
+
+    /// Maps pixel `x`, `y` of the canvas to its tile (column, row) and dot on the tile.
+    /// Returns `None` for pixels outside the canvas.
+    pub fn locate(&self, x: u16, y: u16) -> Option<(usize, usize, Dot<DV>)> {
+        let (sinks, lines) = (DV::NUM_CURRENT_SINKS as u16, DV::NUM_LINES as u16);
+        let (col, row) = ((x / sinks) as usize, (y / lines) as usize);
+        let orientation = self.orientations.get(row)?.get(col)?;
+
+        let (mut cs, mut line) = (x % sinks, y % lines);
+        if orientation.flip_x {
+            cs = sinks - 1 - cs;
+        }
+        if orientation.flip_y {
+            line = lines - 1 - line;
+        }
+
+        Some((col, row, Dot::from_line_cs(line as u8, cs as u8)?))
+    }
+
+    /// PWM value of pixel `x`, `y`, `None` if the pixel is outside the canvas.
+    pub fn get(&self, x: u16, y: u16) -> Option<T> {
+        let (col, row, dot) = self.locate(x, y)?;
+        self.tiles[row][col].get(dot.index())
+    }
+
+    /// Sets pixel `x`, `y` to `value`. Pixels outside the canvas are ignored.
+    pub fn set(&mut self, x: u16, y: u16, value: T) {
+        if let Some((col, row, dot)) = self.locate(x, y) {
+            self.tiles[row][col].set(dot.index(), value);
+        }
+    }
+
+    /// Sets all pixels to `value`.
+    pub fn fill(&mut self, value: T) {
+        self.tiles
+            .iter_mut()
+            .flatten()
+            .for_each(|tile| tile.fill(value));
+    }
+
+    /// Writes the dirty range of every tile to its driver, `drivers[row][col]` driving
+    /// the tile at the same position. Stops at the first failing driver, tiles not
+    /// written stay dirty.
+    pub fn flush<I, DM, IE>(
+        &mut self,
+        drivers: &mut [[Lp586x<DV, I, DM>; COLS]; ROWS],
+    ) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.tiles
+            .iter_mut()
+            .flatten()
+            .zip(drivers.iter_mut().flatten())
+            .try_for_each(|(tile, driver)| tile.flush(driver))
+    }
+}
+
+impl<DV, T, const COLS: usize, const ROWS: usize> Default for DisplayGrid<DV, T, COLS, ROWS>
+where
+    DV: DeviceVariant,
+    T: Copy + Default + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5862, Variant2,
+    };
+
+    #[test]
+    fn test_display_grid() {
+        let mut grid = DisplayGrid::<Variant2, u8, 2, 2>::new()
+            .with_orientation(1, 0, TileOrientation::new().with_flip_x(true))
+            .with_orientation(0, 1, TileOrientation::rotated_180());
+        assert_eq!((grid.width(), grid.height()), (36, 4));
+
+        grid.set(18, 0, 5);
+        grid.set(0, 2, 7);
+        grid.set(35, 3, 9);
+        grid.set(36, 0, 1);
+
+        assert_eq!(grid.tile(1, 0).get(17), Some(5));
+        assert_eq!(grid.tile(0, 1).get(35), Some(7));
+        assert_eq!(grid.tile(1, 1).get(35), Some(9));
+        assert_eq!(grid.get(18, 0), Some(5));
+        assert_eq!(grid.get(36, 0), None);
+
+        let mut frames = [[vec![0; 36], vec![0; 36]], [vec![0; 36], vec![0; 36]]];
+        frames[0][1][17] = 5;
+        frames[1][0][35] = 7;
+        frames[1][1][35] = 9;
+
+        let mut drivers = frames.map(|row| {
+            row.map(|frame| {
+                Lp5862::new(MockInterface::new(vec![
+                    Access::WriteRegister(0x0a9, 0xff),
+                    Access::WriteRegister(0x000, 1),
+                    Access::WriteRegisters(0x200, frame),
+                ]))
+                .unwrap()
+                .into_8bit_data_mode()
+                .unwrap()
+            })
+        });
+        grid.flush(&mut drivers).unwrap();
+        grid.flush(&mut drivers).unwrap();
+
+        drivers
+            .into_iter()
+            .flatten()
+            .for_each(|driver| driver.release().done());
+    }
+}
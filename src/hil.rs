@@ -0,0 +1,96 @@
+//! Hardware-in-the-loop test sequence, meant to be run on target with a panel attached.
+//!
+//! The sequence configures the chip, verifies register read back, shows test patterns
+//! and checks for LED faults. Results are logged through `defmt` and returned as a
+//! [`HilReport`].
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    configuration::Configuration, debug_pattern::coordinate_pattern_value,
+    interface::RegisterAccess, register::Register, self_test::SelfTestReport, DataMode8Bit,
+    DataRefMode, DeviceVariant, Error, Lp586x, PwmAccess,
+};
+
+/// Result of [`run_hil_sequence`].
+#[derive(Debug)]
+pub struct HilReport {
+    /// Device configuration registers read back as written
+    pub configuration_readback: bool,
+    /// PWM registers read back as written
+    pub pwm_readback: bool,
+    /// Fault check result
+    pub self_test: SelfTestReport,
+}
+
+impl HilReport {
+    /// True, if all steps of the sequence passed.
+    pub fn passed(&self) -> bool {
+        self.configuration_readback && self.pwm_readback && self.self_test.passed()
+    }
+}
+
+/// Runs the hardware-in-the-loop test sequence on `driver`.
+///
+/// The chip is configured for Mode 1, so no VSYNC is needed. Time between patterns
+/// is `pattern_time_us`.
+pub fn run_hil_sequence<DV, I, IE, D>(
+    driver: &mut Lp586x<DV, I, DataMode8Bit>,
+    delay: &mut D,
+    pattern_time_us: u32,
+) -> Result<HilReport, Error<IE>>
+where
+    DV: DeviceVariant,
+    I: RegisterAccess<Error = Error<IE>>,
+    D: DelayUs<u32>,
+{
+    defmt::info!("hil: configure");
+    let configuration = Configuration::new::<DV>().with_data_ref_mode(DataRefMode::Mode1);
+    driver.configure(&configuration)?;
+
+    let mut registers = [0u8; 4];
+    driver
+        .interface
+        .read_registers(Register::DEV_INITIAL, &mut registers)?;
+    let configuration_readback = registers == configuration.register_values();
+    if !configuration_readback {
+        defmt::error!(
+            "hil: configuration read back mismatch {=[u8]}",
+            &registers[..]
+        );
+    }
+
+    defmt::info!("hil: patterns");
+    driver.set_global_brightness(0xff)?;
+    driver.show_coordinate_pattern(Some(0))?;
+    delay.delay_us(pattern_time_us);
+    driver.show_coordinate_pattern(None)?;
+    delay.delay_us(pattern_time_us);
+
+    let mut pwm_readback = true;
+    for dot in 0..DV::NUM_DOTS {
+        let value = driver.get_pwm(dot)?;
+        if value != coordinate_pattern_value::<DV>(dot, None) {
+            defmt::error!("hil: PWM read back mismatch on dot {=u16}", dot);
+            pwm_readback = false;
+        }
+    }
+
+    defmt::info!("hil: fault check");
+    let self_test = driver.run_self_test(delay)?;
+    for dot in self_test.open_dots() {
+        defmt::warn!("hil: dot {=u16} open", dot);
+    }
+    for dot in self_test.short_dots() {
+        defmt::warn!("hil: dot {=u16} shorted", dot);
+    }
+
+    let report = HilReport {
+        configuration_readback,
+        pwm_readback,
+        self_test,
+    };
+    defmt::info!("hil: passed: {=bool}", report.passed());
+
+    Ok(report)
+}
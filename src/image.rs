@@ -0,0 +1,299 @@
+//! Decoders for compact grayscale images, e.g. assets kept in flash.
+//!
+//! The decoders are iterators yielding one 8 bit value per pixel, row by row. They
+//! decode on the fly, so an image can be drawn into a frame (e.g. a page of
+//! [`crate::pages::Pages`]) with [`blit`] without a decoded copy in RAM. For
+//! 16 bit data mode, map the values, e.g. with `.map(|v| v as u16 * 257)`, or use
+//! [`Dithered`] to hide the banding of low bit depth assets.
+
+/// Packed 4 bit grayscale, two pixels per byte with the first pixel in the high nibble.
+/// Levels 0..=15 are expanded to 0..=255.
+#[derive(Debug, Clone)]
+pub struct Gray4<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Gray4<'a> {
+    /// Decodes `data`, yielding `2 * data.len()` pixels.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, index: 0 }
+    }
+}
+
+impl Iterator for Gray4<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.index / 2)?;
+        let level = (byte >> ((1 - self.index % 2) * 4)) & 0x0f;
+        self.index += 1;
+
+        Some(level * 17)
+    }
+}
+
+/// Packed 2 bit grayscale, four pixels per byte with the first pixel in the highest
+/// bits. Levels 0..=3 are expanded to 0..=255.
+#[derive(Debug, Clone)]
+pub struct Gray2<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Gray2<'a> {
+    /// Decodes `data`, yielding `4 * data.len()` pixels.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, index: 0 }
+    }
+}
+
+impl Iterator for Gray2<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.index / 4)?;
+        let level = (byte >> ((3 - self.index % 4) * 2)) & 0b11;
+        self.index += 1;
+
+        Some(level * 85)
+    }
+}
+
+/// Dithering applied by [`Dithered`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dither {
+    /// Plain expansion to full scale
+    #[default]
+    None,
+    /// 4x4 ordered (Bayer) dithering of up to half a source level, which breaks up the
+    /// visible steps of smooth gradients
+    Ordered,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Expands 8 bit pixels decoded from a `bits` bit source (e.g. [`Gray4`]) to full
+/// scale 16 bit values for the 16 bit data mode, optionally dithered. Pixels that are
+/// off or at full scale are never dithered.
+#[derive(Debug, Clone)]
+pub struct Dithered<I> {
+    pixels: I,
+    step: u32,
+    width: usize,
+    dither: Dither,
+    index: usize,
+}
+
+impl<I: Iterator<Item = u8>> Dithered<I> {
+    /// Expands `pixels` of an image `width` pixels wide, decoded from a source with
+    /// `bits` bits per pixel.
+    pub fn new(pixels: I, bits: u8, width: usize, dither: Dither) -> Self {
+        Self {
+            pixels,
+            step: u16::MAX as u32 / ((1u32 << bits.clamp(1, 8)) - 1),
+            width: width.max(1),
+            dither,
+            index: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Dithered<I> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        let value = self.pixels.next()? as u16 * 257;
+        let (x, y) = (self.index % self.width, self.index / self.width);
+        self.index += 1;
+
+        if self.dither == Dither::None || value == 0 || value == u16::MAX {
+            return Some(value);
+        }
+
+        // threshold of 0..=15 mapped to -15/32..=15/32 of a source step
+        let threshold = BAYER_4X4[y % 4][x % 4] as i32 * 2 - 15;
+        let offset = threshold * self.step as i32 / 32;
+
+        Some((value as i32 + offset).clamp(0, u16::MAX as i32) as u16)
+    }
+}
+
+/// Run length encoded 8 bit grayscale, as `(count, value)` byte pairs. Runs with a
+/// count of 0 are skipped, a trailing odd byte is ignored.
+#[derive(Debug, Clone)]
+pub struct Rle<'a> {
+    runs: core::slice::ChunksExact<'a, u8>,
+    value: u8,
+    remaining: u8,
+}
+
+impl<'a> Rle<'a> {
+    /// Decodes `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            runs: data.chunks_exact(2),
+            value: 0,
+            remaining: 0,
+        }
+    }
+}
+
+impl Iterator for Rle<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.remaining == 0 {
+            let run = self.runs.next()?;
+            self.remaining = run[0];
+            self.value = run[1];
+        }
+        self.remaining -= 1;
+
+        Some(self.value)
+    }
+}
+
+/// 8 bit grayscale image, row by row, e.g. one frame of a sprite sheet kept in flash.
+/// Pixels equal to the transparent key value are not drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sprite<'a> {
+    pixels: &'a [u8],
+    width: u8,
+    key: Option<u8>,
+}
+
+impl<'a> Sprite<'a> {
+    /// Sprite `width` pixels wide, without transparency. A trailing incomplete row of
+    /// `pixels` is ignored.
+    pub const fn new(pixels: &'a [u8], width: u8) -> Self {
+        Self {
+            pixels,
+            width,
+            key: None,
+        }
+    }
+
+    /// Sprite with pixels of value `key` being transparent.
+    pub const fn with_key(mut self, key: u8) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Frame `index` of a sprite sheet holding frames of `width` x `height` pixels one
+    /// after another, `None` past the last frame.
+    pub fn from_sheet(sheet: &'a [u8], width: u8, height: u8, index: usize) -> Option<Self> {
+        let len = width as usize * height as usize;
+        let start = index.checked_mul(len)?;
+
+        sheet
+            .get(start..start.checked_add(len)?)
+            .map(|pixels| Self::new(pixels, width))
+    }
+
+    pub const fn width(&self) -> u8 {
+        self.width
+    }
+
+    pub const fn height(&self) -> u8 {
+        if self.width == 0 {
+            return 0;
+        }
+        (self.pixels.len() / self.width as usize) as u8
+    }
+
+    /// Value of the pixel at `x`, `y`, `None` if it is transparent or outside.
+    pub fn pixel(&self, x: u8, y: u8) -> Option<u8> {
+        if x >= self.width || y >= self.height() {
+            return None;
+        }
+
+        let value = self.pixels[y as usize * self.width as usize + x as usize];
+        (self.key != Some(value)).then_some(value)
+    }
+}
+
+/// Draws an image `image_width` pixels wide from `pixels` into `frame`, a row major
+/// frame `frame_width` pixels wide, with the top left corner at (`x`, `y`). Pixels
+/// outside the frame are clipped. Returns the number of pixels consumed.
+pub fn blit<T>(
+    frame: &mut [T],
+    frame_width: usize,
+    x: usize,
+    y: usize,
+    image_width: usize,
+    pixels: impl IntoIterator<Item = T>,
+) -> usize {
+    let image_width = image_width.max(1);
+    let mut consumed = 0;
+
+    for (i, value) in pixels.into_iter().enumerate() {
+        consumed = i + 1;
+
+        let column = x + i % image_width;
+        let row = y + i / image_width;
+        if column >= frame_width {
+            continue;
+        }
+        if let Some(pixel) = frame.get_mut(row * frame_width + column) {
+            *pixel = value;
+        }
+    }
+
+    consumed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gray4() {
+        let pixels: Vec<u8> = Gray4::new(&[0x0f, 0x81]).collect();
+        assert_eq!(pixels, vec![0, 255, 136, 17]);
+    }
+
+    #[test]
+    fn test_gray2() {
+        let pixels: Vec<u8> = Gray2::new(&[0b00_01_10_11]).collect();
+        assert_eq!(pixels, vec![0, 85, 170, 255]);
+    }
+
+    #[test]
+    fn test_dithered() {
+        let plain: Vec<u16> =
+            Dithered::new(Gray2::new(&[0b01_01_01_01]), 2, 2, Dither::None).collect();
+        assert_eq!(plain, vec![0x5555; 4]);
+
+        let dithered: Vec<u16> =
+            Dithered::new(Gray2::new(&[0b01_01_00_11]), 2, 2, Dither::Ordered).collect();
+        // step 0x5555: thresholds 0 and 8 of the first row give -15/32 and +1/32 steps
+        assert_eq!(dithered, vec![0x5555 - 0x27ff, 0x5555 + 0x2aa, 0, 0xffff]);
+    }
+
+    #[test]
+    fn test_rle() {
+        let pixels: Vec<u8> = Rle::new(&[2, 7, 0, 9, 1, 3, 5]).collect();
+        assert_eq!(pixels, vec![7, 7, 3]);
+    }
+
+    #[test]
+    fn test_sprite() {
+        let sheet = [1, 2, 3, 4, 5, 6, 7, 8];
+        let sprite = Sprite::from_sheet(&sheet, 2, 2, 1).unwrap().with_key(6);
+        assert_eq!((sprite.width(), sprite.height()), (2, 2));
+        assert_eq!(sprite.pixel(0, 0), Some(5));
+        assert_eq!(sprite.pixel(1, 0), None);
+        assert_eq!(sprite.pixel(0, 2), None);
+        assert!(Sprite::from_sheet(&sheet, 2, 2, 2).is_none());
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut frame = [0u8; 4 * 3];
+        let consumed = blit(&mut frame, 4, 2, 1, 3, Rle::new(&[6, 1]));
+
+        assert_eq!(consumed, 6);
+        assert_eq!(frame, [0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Instrumentation of the register writes issued by the driver, for tuning transfer
+//! sizes and bus clocks.
+//!
+//! Wrap the interface in an [`InstrumentedInterface`] and look at the distribution of
+//! write sizes, e.g. to pick a `max_transfer_size` of the HAL that covers full frames,
+//! or to spot many small writes worth merging.
+
+use crate::{interface::RegisterAccess, Lp586x};
+
+/// Number of buckets of a [`WriteSizeHistogram`]
+pub const WRITE_SIZE_BUCKETS: usize = 11;
+
+/// Histogram of write sizes in bytes, in power of two buckets: bucket 0 counts writes
+/// of 1 byte, bucket `n` writes of `2^n..2^(n+1)` bytes. The last bucket also counts
+/// all larger writes. Counts saturate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteSizeHistogram {
+    buckets: [u32; WRITE_SIZE_BUCKETS],
+    bytes: u32,
+}
+
+impl WriteSizeHistogram {
+    /// Bucket counting writes of `len` bytes.
+    pub const fn bucket_for(len: usize) -> usize {
+        let bucket = (usize::BITS - 1).saturating_sub(len.leading_zeros()) as usize;
+        if bucket < WRITE_SIZE_BUCKETS {
+            bucket
+        } else {
+            WRITE_SIZE_BUCKETS - 1
+        }
+    }
+
+    /// Counts a write of `len` bytes.
+    pub fn record(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+
+        let bucket = &mut self.buckets[Self::bucket_for(len)];
+        *bucket = bucket.saturating_add(1);
+        self.bytes = self.bytes.saturating_add(len as u32);
+    }
+
+    /// Counts of all buckets.
+    pub fn buckets(&self) -> &[u32; WRITE_SIZE_BUCKETS] {
+        &self.buckets
+    }
+
+    /// Range of write sizes counted by `bucket`, as smallest and largest size.
+    pub const fn bucket_range(bucket: usize) -> (usize, usize) {
+        if bucket + 1 >= WRITE_SIZE_BUCKETS {
+            (1 << (WRITE_SIZE_BUCKETS - 1), usize::MAX)
+        } else {
+            (1 << bucket, (1 << (bucket + 1)) - 1)
+        }
+    }
+
+    /// Number of writes counted.
+    pub fn writes(&self) -> u32 {
+        self.buckets
+            .iter()
+            .fold(0u32, |writes, &count| writes.saturating_add(count))
+    }
+
+    /// Number of bytes written in total.
+    pub fn bytes(&self) -> u32 {
+        self.bytes
+    }
+
+    /// Clears all counts.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Interface recording the size of every register write passed on to `I` in a
+/// [`WriteSizeHistogram`]. Sizes are those issued by the driver, before the interface
+/// splits them into bus transfers.
+#[derive(Debug)]
+pub struct InstrumentedInterface<I> {
+    interface: I,
+    histogram: WriteSizeHistogram,
+}
+
+impl<I> InstrumentedInterface<I> {
+    /// Wraps `interface` with an empty histogram.
+    pub fn new(interface: I) -> Self {
+        Self {
+            interface,
+            histogram: WriteSizeHistogram::default(),
+        }
+    }
+
+    /// Write sizes recorded so far.
+    pub fn histogram(&self) -> &WriteSizeHistogram {
+        &self.histogram
+    }
+
+    /// Write sizes recorded so far, e.g. for resetting them.
+    pub fn histogram_mut(&mut self) -> &mut WriteSizeHistogram {
+        &mut self.histogram
+    }
+
+    /// Destroys the wrapper and returns the interface.
+    pub fn release(self) -> I {
+        self.interface
+    }
+}
+
+impl<I: RegisterAccess> RegisterAccess for InstrumentedInterface<I> {
+    type Error = I::Error;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        self.interface.max_recommended_bus_hz()
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        self.interface.max_contiguous_write(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        self.interface.max_contiguous_read(start_register)
+    }
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.interface.read_registers(start_register, data)
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        self.histogram.record(data.len());
+        self.interface.write_registers(start_register, data)
+    }
+}
+
+impl<DV, I, DM> Lp586x<DV, InstrumentedInterface<I>, DM> {
+    /// Write sizes recorded by the instrumented interface.
+    pub fn write_size_histogram(&self) -> &WriteSizeHistogram {
+        self.interface.histogram()
+    }
+
+    /// Destroys the driver and returns the instrumented interface.
+    pub fn release(self) -> InstrumentedInterface<I> {
+        self.interface
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5860, PwmAccess,
+    };
+
+    #[test]
+    fn test_write_size_histogram() {
+        assert_eq!(WriteSizeHistogram::bucket_for(1), 0);
+        assert_eq!(WriteSizeHistogram::bucket_for(3), 1);
+        assert_eq!(WriteSizeHistogram::bucket_for(396), 8);
+        assert_eq!(WriteSizeHistogram::bucket_for(5000), 10);
+        assert_eq!(WriteSizeHistogram::bucket_range(8), (256, 511));
+
+        let frame = [0x0102u16; 198];
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, [2, 1].repeat(198)),
+            Access::WriteRegister(0x005, 0x80),
+        ]);
+
+        let mut ledmatrix = Lp5860::new(InstrumentedInterface::new(interface))
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+        ledmatrix.set_pwm(0, &frame).unwrap();
+        ledmatrix.set_global_brightness(0x80).unwrap();
+
+        let histogram = ledmatrix.write_size_histogram();
+        assert_eq!(histogram.buckets()[0], 3);
+        assert_eq!(histogram.buckets()[8], 1);
+        assert_eq!(histogram.writes(), 4);
+        assert_eq!(histogram.bytes(), 3 + 396);
+
+        ledmatrix.release().release().done();
+    }
+}
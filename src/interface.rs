@@ -1,9 +1,63 @@
-use crate::Error;
+use crate::{chunks::chunks_for_registers, Error};
+
+/// Size of the 10 bit register address space of the LP586x
+pub const REGISTER_ADDRESS_SPACE: usize = 0x400;
+
+/// Number of registers addressable with a single I2C device address
+pub const I2C_REGISTER_PAGE_SIZE: usize = 0x100;
+
+/// Maximum I2C clock frequency supported by the LP586x (Fast-mode Plus).
+pub const I2C_MAX_BUS_HZ: u32 = 1_000_000;
+
+/// Maximum SPI clock frequency supported by the LP586x.
+pub const SPI_MAX_BUS_HZ: u32 = 12_000_000;
+
+/// Minimum time between CS falling and the first SCLK edge, in nanoseconds.
+pub const SPI_CS_SETUP_NS: u32 = 50;
+
+/// Minimum time between the last SCLK edge and CS rising, in nanoseconds.
+pub const SPI_CS_HOLD_NS: u32 = 50;
+
+/// Minimum time CS has to stay high between two transfers, in nanoseconds.
+pub const SPI_CS_HIGH_NS: u32 = 50;
+
+/// Default maximum transfer size of the I2C interfaces: a full register page plus the
+/// register address byte.
+pub const DEFAULT_I2C_MAX_TRANSFER_SIZE: usize = I2C_REGISTER_PAGE_SIZE + 1;
+
+/// Default maximum transfer size of the SPI interfaces: the full register address space
+/// plus the two byte header.
+pub const DEFAULT_SPI_MAX_TRANSFER_SIZE: usize = REGISTER_ADDRESS_SPACE + 2;
 
 /// Trait for giving read and write access to registers
 pub trait RegisterAccess {
     type Error;
 
+    /// Maximum bus clock frequency recommended for this interface, if known.
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        None
+    }
+
+    /// Checks a bus clock frequency against [`RegisterAccess::max_recommended_bus_hz`].
+    /// Running the bus faster than supported corrupts transfers without any error
+    /// being reported by the chip.
+    fn bus_frequency_supported(&self, bus_hz: u32) -> bool {
+        self.max_recommended_bus_hz()
+            .is_none_or(|max_bus_hz| bus_hz <= max_bus_hz)
+    }
+
+    /// Maximum number of registers that can be written with a single call to
+    /// [`RegisterAccess::write_registers`] starting at `start_register`.
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        REGISTER_ADDRESS_SPACE.saturating_sub(start_register as usize)
+    }
+
+    /// Maximum number of registers that can be read with a single call to
+    /// [`RegisterAccess::read_registers`] starting at `start_register`.
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        REGISTER_ADDRESS_SPACE.saturating_sub(start_register as usize)
+    }
+
     /// Reads `N` values from multiple registers, starting from `start_register` and incrementing
     /// the register for every elements.
     fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error>;
@@ -36,23 +90,196 @@ pub trait RegisterAccess {
     }
 }
 
-const fn spi_transmission_header(register: u16, write: bool) -> [u8; 2] {
+/// Access through a mutable reference, so a driver can borrow an interface, e.g. for a
+/// short probe, and `&mut dyn RegisterAccess<Error = _>` can be used to avoid
+/// monomorphizing the driver for every interface type.
+impl<T: RegisterAccess + ?Sized> RegisterAccess for &mut T {
+    type Error = T::Error;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        (**self).max_recommended_bus_hz()
+    }
+
+    fn bus_frequency_supported(&self, bus_hz: u32) -> bool {
+        (**self).bus_frequency_supported(bus_hz)
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        (**self).max_contiguous_write(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        (**self).max_contiguous_read(start_register)
+    }
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read_registers(start_register, data)
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).write_registers(start_register, data)
+    }
+
+    fn read_register(&mut self, register: u16) -> Result<u8, Self::Error> {
+        (**self).read_register(register)
+    }
+
+    fn read_register_wide(&mut self, register: u16) -> Result<u16, Self::Error> {
+        (**self).read_register_wide(register)
+    }
+
+    fn write_register(&mut self, register: u16, value: u8) -> Result<(), Self::Error> {
+        (**self).write_register(register, value)
+    }
+
+    fn write_register_wide(&mut self, register: u16, value: u16) -> Result<(), Self::Error> {
+        (**self).write_register_wide(register, value)
+    }
+}
+
+/// Two byte SPI header preceding the data of a transfer: the 10 bit `register`
+/// address, MSB first, followed by the read/write bit and 5 don't care bits (sent as 0).
+///
+/// Useful for custom interfaces preparing whole transfers in advance, e.g. DMA buffers.
+/// Bits of `register` beyond the 10 bit address space are ignored.
+pub const fn spi_transmission_header(register: u16, write: bool) -> [u8; 2] {
+    let register = register & (REGISTER_ADDRESS_SPACE as u16 - 1);
     [
         (register >> 2) as u8,
         (register << 6) as u8 | if write { 1 << 5 } else { 0 },
     ]
 }
 
+/// Single SPI write of a [`SpiTransferPlan`]: `header` followed by `payload`, with CS
+/// held low for the whole transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpiTransfer<'a> {
+    pub header: [u8; 2],
+    pub payload: &'a [u8],
+}
+
+/// SPI write transfers of a block of consecutive registers, see [`plan_spi_writes`].
+#[derive(Debug, Clone)]
+pub struct SpiTransferPlan<'a> {
+    register: u16,
+    data: &'a [u8],
+    max_payload: usize,
+}
+
+/// Plans the SPI writes of `data` to consecutive registers starting at
+/// `start_register`, with at most `max_payload` bytes per transfer.
+///
+/// The payloads borrow from `data`, so platform code can chain the transfers with DMA
+/// (e.g. on the RP2040) instead of the blocking writes of [`SpiInterface`].
+pub fn plan_spi_writes(
+    start_register: u16,
+    data: &[u8],
+    max_payload: usize,
+) -> SpiTransferPlan<'_> {
+    SpiTransferPlan {
+        register: start_register,
+        data,
+        max_payload: max_payload.max(1),
+    }
+}
+
+impl<'a> Iterator for SpiTransferPlan<'a> {
+    type Item = SpiTransfer<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let (payload, rest) = self.data.split_at(self.data.len().min(self.max_payload));
+        let header = spi_transmission_header(self.register, true);
+
+        self.register = self.register.wrapping_add(payload.len() as u16);
+        self.data = rest;
+
+        Some(SpiTransfer { header, payload })
+    }
+}
+
 use embedded_hal::{blocking::spi, digital::v2::OutputPin};
 
+/// Reason a SPI bus configuration was rejected by [`SpiConfigAdvisor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpiConfigError {
+    /// Clock frequency exceeds [`SPI_MAX_BUS_HZ`]
+    ClockTooFast(u32),
+
+    /// The LP586x samples data on the rising SCLK edge, only SPI modes 0 and 3 work
+    UnsupportedMode(embedded_hal::spi::Mode),
+}
+
+/// Checks a SPI bus configuration against the LP586x datasheet limits.
+pub struct SpiConfigAdvisor;
+
+impl SpiConfigAdvisor {
+    /// Checks clock frequency `bus_hz` and `mode` of the SPI bus.
+    pub fn check(bus_hz: u32, mode: embedded_hal::spi::Mode) -> Result<(), SpiConfigError> {
+        use embedded_hal::spi::{MODE_0, MODE_3};
+
+        if bus_hz > SPI_MAX_BUS_HZ {
+            return Err(SpiConfigError::ClockTooFast(bus_hz));
+        }
+
+        if mode != MODE_0 && mode != MODE_3 {
+            return Err(SpiConfigError::UnsupportedMode(mode));
+        }
+
+        Ok(())
+    }
+}
+
+/// CS handling of [`SpiInterface`] writes split into chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpiCsMode {
+    /// Keep CS asserted across the header and all data chunks, forming a single
+    /// transaction relying on the register address auto-increment
+    #[default]
+    HoldAcrossChunks,
+    /// Deassert CS after every chunk, each chunk is sent with its own header
+    TogglePerChunk,
+}
+
 pub struct SpiInterface<SPI, CS> {
     pub(crate) spi: SPI,
     pub(crate) cs: CS,
+    chunk_size: usize,
+    cs_mode: SpiCsMode,
 }
 
 impl<SPI: spi::Transfer<u8> + spi::Write<u8>, CS: OutputPin> SpiInterface<SPI, CS> {
     pub fn new(spi: SPI, cs: CS) -> Self {
-        Self { spi, cs }
+        Self {
+            spi,
+            cs,
+            chunk_size: REGISTER_ADDRESS_SPACE,
+            cs_mode: SpiCsMode::default(),
+        }
+    }
+
+    /// Writes data in chunks of at most `registers` bytes, e.g. for HALs with a limited
+    /// DMA transfer size. `cs_mode` selects whether CS stays asserted between chunks.
+    pub fn with_chunked_writes(mut self, registers: usize, cs_mode: SpiCsMode) -> Self {
+        self.chunk_size = registers.max(1);
+        self.cs_mode = cs_mode;
+        self
+    }
+
+    /// Like [`SpiInterface::new`], but rejects a bus configured with `bus_hz` and `mode`
+    /// the LP586x does not support (see [`SpiConfigAdvisor`]).
+    pub fn new_checked(
+        spi: SPI,
+        cs: CS,
+        bus_hz: u32,
+        mode: embedded_hal::spi::Mode,
+    ) -> Result<Self, SpiConfigError> {
+        SpiConfigAdvisor::check(bus_hz, mode)?;
+
+        Ok(Self::new(spi, cs))
     }
 
     pub fn release(self) -> (SPI, CS) {
@@ -76,119 +303,730 @@ where
 {
     type Error = Error<SpiInterfaceError<SPIE, CS::Error>>;
 
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        Some(SPI_MAX_BUS_HZ)
+    }
+
     fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
         let header = spi_transmission_header(start_register, false);
 
         self.cs
             .set_low()
             .map_err(SpiInterfaceError::CS)
-            .map_err(Error::Interface)?;
+            .map_err(Error::interface)?;
 
         self.spi
             .write(&header)
             .map_err(SpiInterfaceError::SPI)
-            .map_err(Error::Interface)?;
+            .map_err(Error::interface)?;
 
         self.spi
             .transfer(data)
             .map_err(SpiInterfaceError::SPI)
-            .map_err(Error::Interface)?;
+            .map_err(Error::interface)?;
 
         self.cs
             .set_high()
             .map_err(SpiInterfaceError::CS)
-            .map_err(Error::Interface)?;
+            .map_err(Error::interface)?;
 
         Ok(())
     }
 
     fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
-        let header = spi_transmission_header(start_register, true);
-
-        self.cs
-            .set_low()
-            .map_err(SpiInterfaceError::CS)
-            .map_err(Error::Interface)?;
+        let chunk_size = self.chunk_size;
+        let transaction_len = match self.cs_mode {
+            SpiCsMode::HoldAcrossChunks => data.len(),
+            SpiCsMode::TogglePerChunk => chunk_size,
+        };
 
-        self.spi
-            .write(&header)
-            .map_err(SpiInterfaceError::SPI)
-            .map_err(Error::Interface)?;
-        self.spi
-            .write(data)
-            .map_err(SpiInterfaceError::SPI)
-            .map_err(Error::Interface)?;
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), |_| transaction_len)
+        {
+            let header = spi_transmission_header(register, true);
+
+            self.cs
+                .set_low()
+                .map_err(SpiInterfaceError::CS)
+                .map_err(Error::interface)?;
+
+            self.spi
+                .write(&header)
+                .map_err(SpiInterfaceError::SPI)
+                .map_err(Error::interface)?;
+            for chunk in data[range].chunks(chunk_size) {
+                self.spi
+                    .write(chunk)
+                    .map_err(SpiInterfaceError::SPI)
+                    .map_err(Error::interface)?;
+            }
 
-        self.cs
-            .set_high()
-            .map_err(SpiInterfaceError::CS)
-            .map_err(Error::Interface)?;
+            self.cs
+                .set_high()
+                .map_err(SpiInterfaceError::CS)
+                .map_err(Error::interface)?;
+        }
 
         Ok(())
     }
 }
 
-pub struct I2cInterface<I2C> {
+/// How the I2C interface reads registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum I2cReadMode {
+    /// Register address write and data read in one transaction, joined by a repeated
+    /// start condition
+    #[default]
+    RepeatedStart,
+    /// Register address write terminated by a stop condition, followed by a separate
+    /// read, for I2C controllers unable to issue a repeated start. With embedded-hal
+    /// 0.2 use the `I2cWriteStopReadInterface` instead, which needs the `Read` trait.
+    WriteStopRead,
+}
+
+/// I2C interface. Transfers are split into transactions of at most `MAX_TRANSFER_SIZE`
+/// bytes, including the register address byte.
+pub struct I2cInterface<I2C, const MAX_TRANSFER_SIZE: usize = DEFAULT_I2C_MAX_TRANSFER_SIZE> {
     pub(crate) i2c: I2C,
     pub(crate) address: u8,
+    #[cfg(feature = "eh1_0")]
+    read_mode: I2cReadMode,
 }
 
 impl<I2C> I2cInterface<I2C> {
     pub fn new(i2c: I2C, address: u8) -> Self {
-        Self { i2c, address }
+        Self::with_max_transfer_size(i2c, address)
+    }
+}
+
+impl<I2C, const MAX_TRANSFER_SIZE: usize> I2cInterface<I2C, MAX_TRANSFER_SIZE> {
+    /// Create an interface with a custom maximum transfer size, e.g.
+    /// `I2cInterface::<_, 33>::with_max_transfer_size(i2c, address)`. Larger transfers
+    /// need more RAM with HALs only implementing the basic `Write` trait, but fewer
+    /// transactions.
+    pub fn with_max_transfer_size(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            #[cfg(feature = "eh1_0")]
+            read_mode: I2cReadMode::default(),
+        }
+    }
+
+    /// Selects how registers are read, see [`I2cReadMode`].
+    #[cfg(feature = "eh1_0")]
+    pub fn with_read_mode(mut self, read_mode: I2cReadMode) -> Self {
+        self.read_mode = read_mode;
+        self
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
     }
 
     fn address_with_register(&self, register: u16) -> u8 {
-        // The `address` is the 7bit i2c address (so excluding the R/W bit), not 8 bit (incl R/W)
-        (self.address & !0b11) | ((register & 0x300) >> 8) as u8
+        i2c_address_with_register(self.address, register)
+    }
+
+    /// Maximum number of registers in a single transaction starting at `register`.
+    fn max_registers_per_transfer(register: u16) -> usize {
+        i2c_registers_left_in_page(register).min(MAX_TRANSFER_SIZE.saturating_sub(1))
     }
 }
 
+fn i2c_address_with_register(address: u8, register: u16) -> u8 {
+    // The `address` is the 7bit i2c address (so excluding the R/W bit), not 8 bit (incl R/W)
+    (address & !0b11) | ((register & 0x300) >> 8) as u8
+}
+
+/// Number of registers from `register` up to the end of its I2C register page. The
+/// upper register address bits are part of the device address, so a single
+/// transfer can not cross a page boundary.
+fn i2c_registers_left_in_page(register: u16) -> usize {
+    I2C_REGISTER_PAGE_SIZE - (register as usize % I2C_REGISTER_PAGE_SIZE)
+}
+
 #[cfg(not(feature = "eh1_0"))]
 use embedded_hal::blocking::i2c;
 
 #[cfg(not(feature = "eh1_0"))]
-impl<I2C, IE> RegisterAccess for I2cInterface<I2C>
+impl<I2C, IE, const MAX_TRANSFER_SIZE: usize> RegisterAccess
+    for I2cInterface<I2C, MAX_TRANSFER_SIZE>
 where
     I2C: i2c::Write<Error = IE> + i2c::WriteRead<Error = IE>,
 {
     type Error = Error<IE>;
 
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        Some(I2C_MAX_BUS_HZ)
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        Self::max_registers_per_transfer(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        Self::max_registers_per_transfer(start_register)
+    }
+
     fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
-        self.i2c
-            .write_read(
-                self.address_with_register(start_register),
-                &[start_register as u8],
-                data,
-            )
-            .map_err(Error::Interface)
+        if MAX_TRANSFER_SIZE < 2 {
+            Err(Error::BufferOverrun)?
+        }
+
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+        {
+            self.i2c
+                .write_read(
+                    self.address_with_register(register),
+                    &[register as u8],
+                    &mut data[range],
+                )
+                .map_err(Error::interface)?;
+        }
+
+        Ok(())
     }
 
     fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
-        /// Should be enough for any LP586x device variant, as we only have a 10bit register address space in the LP586x
-        /// Possibly we can set this buffer size appropriately, based on the LP586x variant somehow... for now we
-        /// just allocate this buffe 'large enough' for all cases
-        const MAX_DATA_SIZE: usize = 0x400;
-
-        let data_len = data.len();
-        if data_len > MAX_DATA_SIZE {
+        if MAX_TRANSFER_SIZE < 2 {
             Err(Error::BufferOverrun)?
         }
 
         // create buffer to hold our "wide" address header and data in, for 'legacy/basic' I2C-hal support (meh..)
         // This is wasteful, but needded (?) to support the 'legacy' i2c `Write` trait (for HALs not implementing the
         // `WriteIter` and/or `Transactional` i2c traits e.g. the nrf-hal)
-        let mut buffer = [start_register as u8; MAX_DATA_SIZE + 1];
-        let data_slice = &mut buffer[1..data_len + 1];
+        let mut buffer = [0u8; MAX_TRANSFER_SIZE];
 
-        assert!(data_slice.len() == data.len());
-        data_slice.copy_from_slice(data);
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+        {
+            let chunk = &data[range];
+            buffer[0] = register as u8;
+            buffer[1..=chunk.len()].copy_from_slice(chunk);
+
+            self.i2c
+                .write(
+                    self.address_with_register(register),
+                    &buffer[..=chunk.len()],
+                )
+                .map_err(Error::interface)?;
+        }
 
-        let composite_bytes = &buffer[..data_len + 1];
+        Ok(())
+    }
+}
+
+/// I2C interface for controllers unable to issue a repeated start: registers are read
+/// with a register address write terminated by a stop condition, followed by a
+/// separate read (see [`I2cReadMode::WriteStopRead`]). Unlike [`I2cInterface`] this
+/// needs the HAL to implement the `Read` trait.
+#[cfg(not(feature = "eh1_0"))]
+pub struct I2cWriteStopReadInterface<
+    I2C,
+    const MAX_TRANSFER_SIZE: usize = DEFAULT_I2C_MAX_TRANSFER_SIZE,
+> {
+    interface: I2cInterface<I2C, MAX_TRANSFER_SIZE>,
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C> I2cWriteStopReadInterface<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self::with_max_transfer_size(i2c, address)
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C, const MAX_TRANSFER_SIZE: usize> I2cWriteStopReadInterface<I2C, MAX_TRANSFER_SIZE> {
+    /// Create an interface with a custom maximum transfer size, see
+    /// [`I2cInterface::with_max_transfer_size`].
+    pub fn with_max_transfer_size(i2c: I2C, address: u8) -> Self {
+        Self {
+            interface: I2cInterface::with_max_transfer_size(i2c, address),
+        }
+    }
+
+    pub fn release(self) -> I2C {
+        self.interface.release()
+    }
+}
 
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C, IE, const MAX_TRANSFER_SIZE: usize> RegisterAccess
+    for I2cWriteStopReadInterface<I2C, MAX_TRANSFER_SIZE>
+where
+    I2C: i2c::Write<Error = IE> + i2c::WriteRead<Error = IE> + i2c::Read<Error = IE>,
+{
+    type Error = Error<IE>;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        self.interface.max_recommended_bus_hz()
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        self.interface.max_contiguous_write(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        self.interface.max_contiguous_read(start_register)
+    }
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        if MAX_TRANSFER_SIZE < 2 {
+            Err(Error::BufferOverrun)?
+        }
+
+        for (register, range) in chunks_for_registers(
+            start_register,
+            data.len(),
+            I2cInterface::<I2C, MAX_TRANSFER_SIZE>::max_registers_per_transfer,
+        ) {
+            let address = self.interface.address_with_register(register);
+
+            self.interface
+                .i2c
+                .write(address, &[register as u8])
+                .and_then(|()| self.interface.i2c.read(address, &mut data[range]))
+                .map_err(Error::interface)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        self.interface.write_registers(start_register, data)
+    }
+}
+
+/// I2C interface for HALs implementing the `WriteIter` trait. The register address
+/// and data are streamed to the bus, so unlike [`I2cInterface`] no copy buffer is
+/// needed and every transfer spans a whole register page.
+#[cfg(not(feature = "eh1_0"))]
+pub struct I2cWriteIterInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+/// I2C interface for HALs implementing the `Transactional` trait. Register address
+/// and data are sent as separate operations of a single transaction, so unlike
+/// [`I2cInterface`] no copy buffer is needed and every transfer spans a whole
+/// register page.
+#[cfg(not(feature = "eh1_0"))]
+pub struct I2cTransactionalInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C> I2cWriteIterInterface<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    pub fn release(self) -> I2C {
         self.i2c
-            .write(self.address_with_register(start_register), composite_bytes)
-            .map_err(Error::Interface)
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C> I2cTransactionalInterface<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C, IE> RegisterAccess for I2cWriteIterInterface<I2C>
+where
+    I2C: i2c::WriteIter<Error = IE> + i2c::WriteRead<Error = IE>,
+{
+    type Error = Error<IE>;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        Some(I2C_MAX_BUS_HZ)
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        i2c_registers_left_in_page(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        i2c_registers_left_in_page(start_register)
+    }
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), i2c_registers_left_in_page)
+        {
+            self.i2c
+                .write_read(
+                    i2c_address_with_register(self.address, register),
+                    &[register as u8],
+                    &mut data[range],
+                )
+                .map_err(Error::interface)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), i2c_registers_left_in_page)
+        {
+            let bytes = core::iter::once(register as u8).chain(data[range].iter().copied());
+
+            i2c::WriteIter::write(
+                &mut self.i2c,
+                i2c_address_with_register(self.address, register),
+                bytes,
+            )
+            .map_err(Error::interface)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<I2C, IE> RegisterAccess for I2cTransactionalInterface<I2C>
+where
+    I2C: i2c::Transactional<Error = IE>,
+{
+    type Error = Error<IE>;
+
+    fn max_recommended_bus_hz(&self) -> Option<u32> {
+        Some(I2C_MAX_BUS_HZ)
+    }
+
+    fn max_contiguous_write(&self, start_register: u16) -> usize {
+        i2c_registers_left_in_page(start_register)
+    }
+
+    fn max_contiguous_read(&self, start_register: u16) -> usize {
+        i2c_registers_left_in_page(start_register)
+    }
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), i2c_registers_left_in_page)
+        {
+            self.i2c
+                .exec(
+                    i2c_address_with_register(self.address, register),
+                    &mut [
+                        i2c::Operation::Write(&[register as u8]),
+                        i2c::Operation::Read(&mut data[range]),
+                    ],
+                )
+                .map_err(Error::interface)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        for (register, range) in
+            chunks_for_registers(start_register, data.len(), i2c_registers_left_in_page)
+        {
+            // adjacent write operations are sent without a restart in between
+            self.i2c
+                .exec(
+                    i2c_address_with_register(self.address, register),
+                    &mut [
+                        i2c::Operation::Write(&[register as u8]),
+                        i2c::Operation::Write(&data[range]),
+                    ],
+                )
+                .map_err(Error::interface)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, not(feature = "eh1_0")))]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeI2c {
+        writes: Vec<(u8, Vec<u8>)>,
+    }
+
+    impl i2c::Write for FakeI2c {
+        type Error = ();
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.writes.push((address, bytes.to_vec()));
+            Ok(())
+        }
+    }
+
+    impl i2c::WriteRead for FakeI2c {
+        type Error = ();
+
+        fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), ()> {
+            self.writes.push((address, bytes.to_vec()));
+            buffer.fill(address);
+            Ok(())
+        }
+    }
+
+    impl i2c::Read for FakeI2c {
+        type Error = ();
+
+        fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            buffer.fill(!address);
+            Ok(())
+        }
+    }
+
+    impl i2c::WriteIter for FakeI2c {
+        type Error = ();
+
+        fn write<B>(&mut self, address: u8, bytes: B) -> Result<(), Self::Error>
+        where
+            B: IntoIterator<Item = u8>,
+        {
+            self.writes.push((address, bytes.into_iter().collect()));
+            Ok(())
+        }
+    }
+
+    impl i2c::Transactional for FakeI2c {
+        type Error = ();
+
+        fn exec(&mut self, address: u8, operations: &mut [i2c::Operation]) -> Result<(), ()> {
+            let mut bytes = Vec::new();
+            for operation in operations {
+                match operation {
+                    i2c::Operation::Write(data) => bytes.extend_from_slice(data),
+                    i2c::Operation::Read(buffer) => buffer.fill(address),
+                }
+            }
+            self.writes.push((address, bytes));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_i2c_streaming_interfaces() {
+        let data: Vec<u8> = (0..0x120).map(|v| v as u8).collect();
+        let expected = vec![
+            (0x40, [&[0x80], &data[..0x80]].concat()),
+            (0x41, [&[0x00], &data[0x80..]].concat()),
+        ];
+
+        let mut write_iter_if = I2cWriteIterInterface::new(FakeI2c::default(), 0x40);
+        write_iter_if.write_registers(0x080, &data).unwrap();
+        assert_eq!(write_iter_if.release().writes, expected);
+
+        let mut transactional_if = I2cTransactionalInterface::new(FakeI2c::default(), 0x40);
+        transactional_if.write_registers(0x080, &data).unwrap();
+        let mut buffer = [0; 2];
+        transactional_if.read_registers(0x1ff, &mut buffer).unwrap();
+        assert_eq!(buffer, [0x41, 0x42]);
+        assert_eq!(transactional_if.release().writes[..2], expected);
+    }
+
+    #[test]
+    fn test_plan_spi_writes() {
+        let data = [1, 2, 3, 4, 5];
+        let transfers: Vec<_> = plan_spi_writes(0x2fe, &data, 2).collect();
+
+        assert_eq!(
+            transfers,
+            vec![
+                SpiTransfer {
+                    header: spi_transmission_header(0x2fe, true),
+                    payload: &data[..2],
+                },
+                SpiTransfer {
+                    header: spi_transmission_header(0x300, true),
+                    payload: &data[2..4],
+                },
+                SpiTransfer {
+                    header: spi_transmission_header(0x302, true),
+                    payload: &data[4..],
+                },
+            ]
+        );
+        assert_eq!(plan_spi_writes(0x200, &[], 2).count(), 0);
+    }
+
+    #[test]
+    fn test_spi_transmission_header() {
+        assert_eq!(spi_transmission_header(0x000, false), [0x00, 0x00]);
+        assert_eq!(spi_transmission_header(0x3ff, true), [0xff, 0xe0]);
+        assert_eq!(spi_transmission_header(0x401, true), [0x00, 0x60]);
+
+        for register in 0..REGISTER_ADDRESS_SPACE as u16 {
+            for write in [false, true] {
+                let [high, low] = spi_transmission_header(register, write);
+
+                assert_eq!((high as u16) << 2 | (low >> 6) as u16, register);
+                assert_eq!(low & (1 << 5) != 0, write);
+                assert_eq!(low & 0b1_1111, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_i2c_write_stop_read() {
+        let mut i2c_if = I2cWriteStopReadInterface::new(FakeI2c::default(), 0x40);
+
+        let mut data = [0u8; 2];
+        i2c_if.read_registers(0x064, &mut data).unwrap();
+        assert_eq!(data, [!0x40, !0x40]);
+        assert_eq!(i2c_if.release().writes, vec![(0x40, vec![0x64])]);
+    }
+
+    /// Bus implementing only `Write` and `WriteRead`, like the baseline requirement
+    struct WriteReadI2c;
+
+    impl i2c::Write for WriteReadI2c {
+        type Error = ();
+
+        fn write(&mut self, _address: u8, _bytes: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl i2c::WriteRead for WriteReadI2c {
+        type Error = ();
+
+        fn write_read(&mut self, _address: u8, _bytes: &[u8], buffer: &mut [u8]) -> Result<(), ()> {
+            buffer.fill(0x5a);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_i2c_without_read() {
+        let mut i2c_if = I2cInterface::new(WriteReadI2c, 0x40);
+
+        assert_eq!(i2c_if.read_register(0x064).unwrap(), 0x5a);
+    }
+
+    #[test]
+    fn test_i2c_chunked_transfers() {
+        let mut i2c_if = I2cInterface::<_, 4>::with_max_transfer_size(FakeI2c::default(), 0x40);
+
+        // chunks are limited by the transfer size and the register page boundary
+        i2c_if.write_registers(0x1fd, &[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(
+            i2c_if.i2c.writes,
+            vec![(0x41, vec![0xfd, 1, 2, 3]), (0x42, vec![0x00, 4, 5])]
+        );
+
+        let mut data = [0u8; 3];
+        i2c_if.read_registers(0x2ff, &mut data).unwrap();
+        assert_eq!(data, [0x42, 0x43, 0x43]);
+
+        assert!(i2c_if.bus_frequency_supported(400_000));
+        assert!(!i2c_if.bus_frequency_supported(3_400_000));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum SpiEvent {
+        CsLow,
+        CsHigh,
+        Write(Vec<u8>),
+    }
+
+    type SpiLog = std::rc::Rc<std::cell::RefCell<Vec<SpiEvent>>>;
+
+    struct FakeSpi(SpiLog);
+
+    impl spi::Write<u8> for FakeSpi {
+        type Error = ();
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(SpiEvent::Write(words.to_vec()));
+            Ok(())
+        }
+    }
+
+    impl spi::Transfer<u8> for FakeSpi {
+        type Error = ();
+
+        fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+            Ok(words)
+        }
+    }
+
+    struct FakeCs(SpiLog);
+
+    impl OutputPin for FakeCs {
+        type Error = ();
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(SpiEvent::CsLow);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(SpiEvent::CsHigh);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_spi_chunked_writes() {
+        use SpiEvent::*;
+
+        let log = SpiLog::default();
+        let mut spi_if = SpiInterface::new(FakeSpi(log.clone()), FakeCs(log.clone()))
+            .with_chunked_writes(2, SpiCsMode::HoldAcrossChunks);
+        spi_if.write_registers(0x200, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            log.take(),
+            vec![
+                CsLow,
+                Write(vec![0x80, 0x20]),
+                Write(vec![1, 2]),
+                Write(vec![3]),
+                CsHigh
+            ]
+        );
+
+        let mut spi_if = spi_if.with_chunked_writes(2, SpiCsMode::TogglePerChunk);
+        spi_if.write_registers(0x200, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            log.take(),
+            vec![
+                CsLow,
+                Write(vec![0x80, 0x20]),
+                Write(vec![1, 2]),
+                CsHigh,
+                CsLow,
+                Write(vec![0x80, 0xa0]),
+                Write(vec![3]),
+                CsHigh
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spi_config_advisor() {
+        use embedded_hal::spi::{MODE_0, MODE_1, MODE_3};
+
+        assert_eq!(SpiConfigAdvisor::check(SPI_MAX_BUS_HZ, MODE_0), Ok(()));
+        assert_eq!(SpiConfigAdvisor::check(1_000_000, MODE_3), Ok(()));
+        assert_eq!(
+            SpiConfigAdvisor::check(20_000_000, MODE_0),
+            Err(SpiConfigError::ClockTooFast(20_000_000))
+        );
+        assert_eq!(
+            SpiConfigAdvisor::check(1_000_000, MODE_1),
+            Err(SpiConfigError::UnsupportedMode(MODE_1))
+        );
     }
 }
 
@@ -197,7 +1035,12 @@ mod for_eh1_0 {
     use super::*;
     use eh1_0::{i2c, spi};
 
-    pub struct SpiDeviceInterface<SPID> {
+    /// SPI interface. Transfers are split into transactions of at most
+    /// `MAX_TRANSFER_SIZE` bytes, including the two byte header.
+    pub struct SpiDeviceInterface<
+        SPID,
+        const MAX_TRANSFER_SIZE: usize = DEFAULT_SPI_MAX_TRANSFER_SIZE,
+    > {
         pub(crate) spi_device: SPID,
     }
 
@@ -205,82 +1048,172 @@ mod for_eh1_0 {
         pub fn new(spi_device: SPID) -> Self {
             Self { spi_device }
         }
+    }
+
+    impl<SPID: spi::SpiDevice, const MAX_TRANSFER_SIZE: usize>
+        SpiDeviceInterface<SPID, MAX_TRANSFER_SIZE>
+    {
+        /// Create an interface with a custom maximum transfer size, e.g.
+        /// `SpiDeviceInterface::<_, 64>::with_max_transfer_size(spi_device)`.
+        pub fn with_max_transfer_size(spi_device: SPID) -> Self {
+            Self { spi_device }
+        }
 
         pub fn release(self) -> SPID {
             self.spi_device
         }
+
+        /// Maximum number of registers in a single transaction.
+        fn max_registers_per_transfer(_register: u16) -> usize {
+            MAX_TRANSFER_SIZE.saturating_sub(2)
+        }
     }
 
-    impl<SPID, IE> RegisterAccess for SpiDeviceInterface<SPID>
+    impl<SPID, IE, const MAX_TRANSFER_SIZE: usize> RegisterAccess
+        for SpiDeviceInterface<SPID, MAX_TRANSFER_SIZE>
     where
         SPID: spi::SpiDevice<Error = IE>,
     {
         type Error = Error<IE>;
 
+        fn max_recommended_bus_hz(&self) -> Option<u32> {
+            Some(SPI_MAX_BUS_HZ)
+        }
+
+        fn max_contiguous_write(&self, start_register: u16) -> usize {
+            Self::max_registers_per_transfer(start_register)
+                .min(REGISTER_ADDRESS_SPACE.saturating_sub(start_register as usize))
+        }
+
+        fn max_contiguous_read(&self, start_register: u16) -> usize {
+            self.max_contiguous_write(start_register)
+        }
+
         fn read_registers(
             &mut self,
             start_register: u16,
             data: &mut [u8],
         ) -> Result<(), Self::Error> {
-            let header = spi_transmission_header(start_register, false);
+            if MAX_TRANSFER_SIZE < 3 {
+                Err(Error::BufferOverrun)?
+            }
 
-            let mut operations = [spi::Operation::Write(&header), spi::Operation::Read(data)];
+            for (register, range) in
+                chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+            {
+                let header = spi_transmission_header(register, false);
 
-            self.spi_device
-                .transaction(&mut operations)
-                .map_err(Error::Interface)?;
+                let mut operations = [
+                    spi::Operation::Write(&header),
+                    spi::Operation::Read(&mut data[range]),
+                ];
+
+                self.spi_device
+                    .transaction(&mut operations)
+                    .map_err(Error::interface)?;
+            }
 
             Ok(())
         }
 
         fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
-            let header = spi_transmission_header(start_register, true);
+            if MAX_TRANSFER_SIZE < 3 {
+                Err(Error::BufferOverrun)?
+            }
 
-            let mut operations = [spi::Operation::Write(&header), spi::Operation::Write(data)];
+            for (register, range) in
+                chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+            {
+                let header = spi_transmission_header(register, true);
 
-            self.spi_device
-                .transaction(&mut operations)
-                .map_err(Error::Interface)?;
+                let mut operations = [
+                    spi::Operation::Write(&header),
+                    spi::Operation::Write(&data[range]),
+                ];
 
-            Ok(())
-        }
-    }
+                self.spi_device
+                    .transaction(&mut operations)
+                    .map_err(Error::interface)?;
+            }
 
-    impl<I2C: i2c::I2c> I2cInterface<I2C> {
-        pub fn release(self) -> I2C {
-            self.i2c
+            Ok(())
         }
     }
 
-    impl<I2C, IE> RegisterAccess for I2cInterface<I2C>
+    impl<I2C, IE, const MAX_TRANSFER_SIZE: usize> RegisterAccess
+        for I2cInterface<I2C, MAX_TRANSFER_SIZE>
     where
         I2C: i2c::I2c<Error = IE>,
     {
         type Error = Error<IE>;
 
+        fn max_recommended_bus_hz(&self) -> Option<u32> {
+            Some(I2C_MAX_BUS_HZ)
+        }
+
+        fn max_contiguous_write(&self, start_register: u16) -> usize {
+            Self::max_registers_per_transfer(start_register)
+        }
+
+        fn max_contiguous_read(&self, start_register: u16) -> usize {
+            Self::max_registers_per_transfer(start_register)
+        }
+
         fn read_registers(
             &mut self,
             start_register: u16,
             data: &mut [u8],
         ) -> Result<(), Self::Error> {
-            let header = [(start_register & 0xff) as u8];
-            let mut operations = [i2c::Operation::Write(&header), i2c::Operation::Read(data)];
+            if MAX_TRANSFER_SIZE < 2 {
+                Err(Error::BufferOverrun)?
+            }
 
-            self.i2c
-                .transaction(self.address_with_register(start_register), &mut operations)
-                .map_err(Error::Interface)?;
+            for (register, range) in
+                chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+            {
+                let header = [(register & 0xff) as u8];
+                let address = self.address_with_register(register);
+
+                match self.read_mode {
+                    I2cReadMode::RepeatedStart => self.i2c.transaction(
+                        address,
+                        &mut [
+                            i2c::Operation::Write(&header),
+                            i2c::Operation::Read(&mut data[range]),
+                        ],
+                    ),
+                    I2cReadMode::WriteStopRead => self
+                        .i2c
+                        .transaction(address, &mut [i2c::Operation::Write(&header)])
+                        .and_then(|()| {
+                            self.i2c
+                                .transaction(address, &mut [i2c::Operation::Read(&mut data[range])])
+                        }),
+                }
+                .map_err(Error::interface)?;
+            }
 
             Ok(())
         }
 
         fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
-            let header = [(start_register & 0xff) as u8];
-
-            let mut operations = [i2c::Operation::Write(&header), i2c::Operation::Write(data)];
+            if MAX_TRANSFER_SIZE < 2 {
+                Err(Error::BufferOverrun)?
+            }
 
-            self.i2c
-                .transaction(self.address_with_register(start_register), &mut operations)
-                .map_err(Error::Interface)?;
+            for (register, range) in
+                chunks_for_registers(start_register, data.len(), Self::max_registers_per_transfer)
+            {
+                let header = [(register & 0xff) as u8];
+                let mut operations = [
+                    i2c::Operation::Write(&header),
+                    i2c::Operation::Write(&data[range]),
+                ];
+
+                self.i2c
+                    .transaction(self.address_with_register(register), &mut operations)
+                    .map_err(Error::interface)?;
+            }
 
             Ok(())
         }
@@ -375,6 +1308,37 @@ mod for_eh1_0 {
 
             i2c_if.release().done();
         }
+
+        #[test]
+        fn test_spi_chunked_write() {
+            let spi = SpiMock::new(&[
+                SpiTransaction::transaction_start(),
+                SpiTransaction::write_vec(vec![0x80, 0x20]),
+                SpiTransaction::write_vec(vec![1, 2]),
+                SpiTransaction::transaction_end(),
+                SpiTransaction::transaction_start(),
+                SpiTransaction::write_vec(vec![0x80, 0xa0]),
+                SpiTransaction::write_vec(vec![3]),
+                SpiTransaction::transaction_end(),
+            ]);
+
+            let mut spi_if = SpiDeviceInterface::<_, 4>::with_max_transfer_size(spi);
+
+            spi_if.write_registers(0x200, &[1, 2, 3]).unwrap();
+
+            spi_if.release().done();
+        }
+
+        #[test]
+        fn test_i2c_max_contiguous_transfer() {
+            let i2c_if = I2cInterface::new(I2cMock::new(&[]), 0);
+
+            assert_eq!(i2c_if.max_contiguous_write(0x200), 0x100);
+            assert_eq!(i2c_if.max_contiguous_write(0x2f0), 0x10);
+            assert_eq!(i2c_if.max_contiguous_read(0x3ff), 1);
+
+            i2c_if.release().done();
+        }
     }
 }
 
@@ -385,6 +1349,14 @@ pub use for_eh1_0::SpiDeviceInterface;
 pub(crate) mod mock {
     use super::RegisterAccess;
     use crate::Error;
+    use embedded_hal::blocking::delay::DelayUs;
+
+    /// Delay returning immediately, for code under test waiting between accesses
+    pub(crate) struct NoDelay;
+
+    impl DelayUs<u32> for NoDelay {
+        fn delay_us(&mut self, _us: u32) {}
+    }
 
     #[derive(Debug)]
     #[allow(dead_code)]
@@ -393,6 +1365,8 @@ pub(crate) mod mock {
         ReadRegisters(u16, Vec<u8>),
         WriteRegister(u16, u8),
         WriteRegisters(u16, Vec<u8>),
+        /// Write starting at the register, failing with an interface error
+        FailWrite(u16),
     }
 
     #[derive(Debug)]
@@ -469,6 +1443,13 @@ pub(crate) mod mock {
                     );
                     assert_eq!(expected_values, data, "Expected data written to register {reg:x} to be {expected_values:x?} but got {data:x?}");
                 }
+                Some(Access::FailWrite(reg)) => {
+                    assert_eq!(
+                        reg, start_register,
+                        "Expected failing write on register {reg:x} but got {start_register:x}"
+                    );
+                    return Err(Error::interface(()));
+                }
                 Some(access) => {
                     panic!("Unexpected register access when expecting WriteRegisters: {access:?}")
                 }
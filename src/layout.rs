@@ -0,0 +1,211 @@
+//! Mapping between logical and physical line order.
+//!
+//! The LP586x always scans its lines in order L0, L1, ... . Boards routing the lines
+//! to display rows out of order can describe the routing once with a [`LineMap`] and
+//! render in logical order. Boards populating only some lines describe the used lines
+//! with [`LineMap::partial`], which leaves the unused lines out of the logical rows.
+
+use core::marker::PhantomData;
+
+use crate::{
+    interface::RegisterAccess, DataModeMarker, DeviceVariant, Dot, Error, Lp586x, MAX_LINES,
+};
+
+/// Maps logical lines (display rows) to the physical lines (switches) driving them.
+#[derive(Debug)]
+pub struct LineMap<DV> {
+    physical: [u8; MAX_LINES],
+    num_lines: u8,
+    _phantom_data: PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant> LineMap<DV> {
+    /// Lines in physical order.
+    pub fn identity() -> Self {
+        let mut physical = [0; MAX_LINES];
+        physical
+            .iter_mut()
+            .enumerate()
+            .for_each(|(logical, line)| *line = logical as u8);
+
+        Self {
+            physical,
+            num_lines: DV::NUM_LINES,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Create a map from `physical`, holding the physical line of every logical line.
+    /// Returns `None` unless `physical` is a permutation of all lines of the variant.
+    pub fn new(physical: &[u8]) -> Option<Self> {
+        if physical.len() != DV::NUM_LINES as usize {
+            return None;
+        }
+
+        Self::partial(physical)
+    }
+
+    /// Create a map of the lines in use from `physical`, holding the physical line of
+    /// every logical line. Lines not in `physical` are unused and have no logical line.
+    /// Returns `None` if `physical` is empty, or holds a line twice or a line the
+    /// variant does not have.
+    pub fn partial(physical: &[u8]) -> Option<Self> {
+        let num_lines = DV::NUM_LINES as usize;
+        if physical.is_empty() || physical.len() > num_lines {
+            return None;
+        }
+
+        let mut seen = [false; MAX_LINES];
+        for &line in physical {
+            if line as usize >= num_lines || seen[line as usize] {
+                return None;
+            }
+            seen[line as usize] = true;
+        }
+
+        let mut map = Self::identity();
+        map.physical[..physical.len()].copy_from_slice(physical);
+        map.num_lines = physical.len() as u8;
+
+        Some(map)
+    }
+
+    /// Number of logical lines, i.e. lines in use.
+    pub fn num_lines(&self) -> u8 {
+        self.num_lines
+    }
+
+    /// True, if physical line `line` drives a logical line.
+    pub fn is_used(&self, line: u8) -> bool {
+        self.physical[..self.num_lines as usize].contains(&line)
+    }
+
+    /// Physical line driving `logical_line`.
+    pub fn physical_line(&self, logical_line: u8) -> u8 {
+        self.physical[logical_line as usize]
+    }
+
+    /// Physical dot of the logical dot at `logical_line` and current sink `cs`.
+    pub fn dot(&self, logical_line: u8, cs: u8) -> Option<Dot<DV>> {
+        if logical_line >= self.num_lines {
+            return None;
+        }
+
+        Dot::from_line_cs(self.physical_line(logical_line), cs)
+    }
+
+    /// Physical dot index of the logical dot index `dot`.
+    pub fn physical_dot(&self, dot: u16) -> Option<u16> {
+        let sinks = DV::NUM_CURRENT_SINKS as u16;
+        let line = u8::try_from(dot / sinks).ok()?;
+
+        self.dot(line, (dot % sinks) as u8).map(|dot| dot.index())
+    }
+
+    /// Reorders a frame of PWM values in logical order into physical order. The
+    /// logical frame holds one value per dot of the lines in use, the physical frame
+    /// one value per dot of the variant. Values of unused lines are left unchanged.
+    ///
+    /// Returns `false` without touching `physical` if either frame has a different
+    /// length.
+    pub fn map_frame<T: Copy>(&self, logical: &[T], physical: &mut [T]) -> bool {
+        let sinks = DV::NUM_CURRENT_SINKS as usize;
+        if logical.len() != self.num_lines as usize * sinks
+            || physical.len() != DV::NUM_DOTS as usize
+        {
+            return false;
+        }
+
+        for (logical_line, values) in logical.chunks(sinks).enumerate() {
+            let line = self.physical_line(logical_line as u8) as usize;
+            physical[line * sinks..(line + 1) * sinks].copy_from_slice(values);
+        }
+
+        true
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Disables the lines unused by `map`, in a single read and write of the dot on/off
+    /// registers. The on/off states of the dots of used lines are kept.
+    pub fn disable_unused_lines(&mut self, map: &LineMap<DV>) -> Result<(), Error<IE>> {
+        self.update_dot_onoff(|line, registers| {
+            if !map.is_used(line) {
+                registers.fill(0);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5864, Variant2, Variant4,
+    };
+
+    #[test]
+    fn test_line_map() {
+        assert!(LineMap::<Variant4>::new(&[0, 1, 2]).is_none());
+        assert!(LineMap::<Variant4>::new(&[0, 1, 1, 3]).is_none());
+        assert!(LineMap::<Variant4>::new(&[0, 1, 2, 4]).is_none());
+
+        let map = LineMap::<Variant4>::new(&[3, 2, 0, 1]).unwrap();
+        assert_eq!(map.physical_line(0), 3);
+        assert_eq!(map.physical_dot(0), Some(3 * 18));
+        assert_eq!(map.physical_dot(18 + 5), Some(2 * 18 + 5));
+        assert_eq!(map.physical_dot(72), None);
+
+        let map = LineMap::<Variant2>::new(&[1, 0]).unwrap();
+        let logical: Vec<u8> = (0..36).collect();
+        let mut physical = [0u8; 36];
+        assert!(map.map_frame(&logical, &mut physical));
+        assert_eq!(physical[..18], logical[18..]);
+        assert_eq!(physical[18..], logical[..18]);
+    }
+
+    #[test]
+    fn test_partial_line_map() {
+        assert!(LineMap::<Variant4>::partial(&[]).is_none());
+        assert!(LineMap::<Variant4>::partial(&[2, 2]).is_none());
+
+        let map = LineMap::<Variant4>::partial(&[0, 2]).unwrap();
+        assert_eq!(map.num_lines(), 2);
+        assert!(map.is_used(2));
+        assert!(!map.is_used(1));
+        assert_eq!(map.physical_dot(18 + 1), Some(2 * 18 + 1));
+        assert_eq!(map.physical_dot(2 * 18), None);
+
+        let logical: Vec<u8> = (1..=36).collect();
+        let mut physical = [0u8; 72];
+        assert!(map.map_frame(&logical, &mut physical));
+        assert_eq!(physical[..18], logical[..18]);
+        assert_eq!(physical[36..54], logical[18..]);
+        assert!(physical[18..36]
+            .iter()
+            .chain(&physical[54..])
+            .all(|&v| v == 0));
+        assert!(!map.map_frame(&logical[..18], &mut physical));
+        assert!(!map.map_frame(&logical, &mut [0u8; 54]));
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x043, [0xff, 0xdf, 0xff, 0xff, 0xff, 0xff].repeat(2)),
+            Access::WriteRegisters(
+                0x043,
+                vec![0xff, 0xdf, 0xff, 0, 0, 0, 0xff, 0xdf, 0xff, 0, 0, 0],
+            ),
+        ]);
+
+        let mut ledmatrix = Lp5864::new(interface).unwrap();
+        ledmatrix.disable_unused_lines(&map).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
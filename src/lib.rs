@@ -4,29 +4,169 @@
 //! Datasheet: <https://www.ti.com/lit/ds/symlink/lp5864.pdf>
 //!
 //! Register map: <https://www.ti.com/lit/ug/snvu786/snvu786.pdf>
+//!
+//! The driver is `no_std` and does not depend on `alloc`. No API allocates: temporary
+//! buffers live on the stack and are sized for the largest device variant (LP5860),
+//! e.g. 396 bytes for a full 16 bit PWM frame.
+//!
+//! Operations of [`Lp586x`] do not panic on dots, lines and current sinks out of
+//! range: these are reported as [`Error::DotOutOfRange`] or clipped, as documented for
+//! each operation. The exceptions are [`Dot::with_index`] and indexing into fixed sized
+//! containers like [`pages::Pages`] with an out of range index. Helpers validating
+//! their setup on construction, e.g. [`brightness::AutoBrightness::new`], document
+//! their panics. The bit packing and transfer planning code is fuzzed with the harness
+//! in `fuzz/`.
+//!
+//! The `max-dots-18`, `-36`, `-72` and `-144` features shrink the internal buffers to
+//! the given number of dots. Unlike most features they are not additive: with one of
+//! them enabled, creating a driver for a larger variant fails to compile, also in any
+//! other crate of the build. Only enable them in the final application, never in a
+//! library depending on this crate.
 
 #![cfg_attr(not(test), no_std)]
 
+pub mod bar_graph;
+pub mod brightness;
+pub mod broadcast;
+pub mod calibration;
+mod chunks;
+pub mod clock;
+pub mod compose;
 pub mod configuration;
+pub mod debug_pattern;
+pub mod draw;
+pub mod fault_poller;
+pub mod framebuffer;
+pub mod grid;
+#[cfg(feature = "hil-test")]
+pub mod hil;
+pub mod image;
+#[cfg(feature = "instrumentation")]
+pub mod instrument;
 pub mod interface;
+pub mod layout;
+pub mod loopback;
+pub mod mask;
+pub mod mirror;
+pub mod pages;
+pub mod profile;
 mod register;
+pub mod self_test;
+#[cfg(feature = "critical-section")]
+pub mod split;
+pub mod strip;
+pub mod supervisor;
+pub mod verify;
+pub mod vsync;
 
 use configuration::Configuration;
 use interface::{RegisterAccess, SpiInterfaceError};
 use register::{BitFlags, Register};
 
+#[cfg(feature = "debug-names")]
+pub use register::register_name;
+
+/// Driver operation during which an interface error occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Op {
+    /// Plain register access, not tagged with a driver operation
+    Register,
+    /// Software reset
+    Reset,
+    /// Enabling or disabling the chip
+    ChipEnable,
+    /// Writing the device configuration
+    Configure,
+    /// Writing PWM values
+    WriteFrame,
+    /// Reading PWM values
+    ReadFrame,
+    /// Writing dot groups or dot currents
+    DotSettings,
+    /// Writing global or group brightness and currents
+    Brightness,
+    /// Reading or clearing fault states
+    Faults,
+}
+
 /// Error enum for the LP586x driver
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error<IE> {
-    /// An interface related error has occured
-    Interface(IE),
+    /// An interface related error has occured during `op`
+    Interface { op: Op, error: IE },
 
     /// Temporary buffer too small
     BufferOverrun,
+
+    /// Addressed dot is outside the device variants capabilities
+    DotOutOfRange,
+
+    /// The interface cannot read registers, e.g. a [`broadcast::BroadcastInterface`]
+    ReadUnsupported,
+}
+
+impl<IE> Error<IE> {
+    /// Wraps an interface error of a plain register access ([`Op::Register`]).
+    pub fn interface(error: IE) -> Self {
+        Error::Interface {
+            op: Op::Register,
+            error,
+        }
+    }
+
+    /// Tags an interface error of a plain register access with `op`. Errors already
+    /// tagged keep the innermost operation.
+    pub fn tagged(self, op: Op) -> Self {
+        match self {
+            Error::Interface {
+                op: Op::Register,
+                error,
+            } => Error::Interface { op, error },
+            error => error,
+        }
+    }
+}
+
+/// How writes extending past the last dot of the device variant are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowBehavior {
+    /// Return [`Error::DotOutOfRange`] without writing anything
+    #[default]
+    Error,
+    /// Write the values that fit, ignore the rest
+    Clamp,
+}
+
+/// How 16 bit PWM values too low for reliable LED open/short detection are handled,
+/// see [`Lp586x::with_low_pwm_policy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LowPwmPolicy {
+    /// Write the values as given
+    #[default]
+    Allow,
+    /// Raise values below [`Lp586x::FAULT_DETECTION_MIN_PWM`] to that threshold, dots
+    /// set to 0 stay off
+    Clamp,
+}
+
+/// Callbacks around every PWM write of a frame (or part of it), e.g. for timestamping
+/// rendering latency. Both are called with the first dot and the number of dots
+/// written; `on_flush_end` is called whether the write succeeded or not.
+#[derive(Debug, Clone, Copy)]
+pub struct FlushHooks {
+    pub on_flush_start: fn(start_dot: u16, dots: usize),
+    pub on_flush_end: fn(start_dot: u16, dots: usize),
 }
 
 /// Output PWM frequency setting
-#[derive(Debug)]
+///
+/// The PWM clock is derived from the internal oscillator, which has no trim or source
+/// selection in the register map. Frequencies are nominal, so timing sensitive
+/// applications should synchronize frames with VSYNC (Mode 2 and Mode 3) rather than
+/// rely on the refresh rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PwmFrequency {
     /// 125 kHz
     Pwm125kHz,
@@ -35,7 +175,7 @@ pub enum PwmFrequency {
 }
 
 /// Line switch blanking time setting
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LineBlankingTime {
     /// 1µs
     Blank1us,
@@ -44,7 +184,7 @@ pub enum LineBlankingTime {
 }
 
 /// Dimming scale setting of final PWM generator
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PwmScaleMode {
     /// Linear scale dimming curve
     Linear,
@@ -53,7 +193,7 @@ pub enum PwmScaleMode {
 }
 
 /// Downside deghosting level selection
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DownDeghost {
     None,
     Weak,
@@ -70,10 +210,19 @@ impl DownDeghost {
             DownDeghost::Strong => 3,
         }
     }
+
+    pub const fn from_register_value(value: u8) -> Self {
+        match value & 0b11 {
+            0 => DownDeghost::None,
+            1 => DownDeghost::Weak,
+            2 => DownDeghost::Medium,
+            _ => DownDeghost::Strong,
+        }
+    }
 }
 
 /// Scan line clamp voltage of upside deghosting
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UpDeghost {
     /// VLED - 2V
     VledMinus2V,
@@ -94,10 +243,52 @@ impl UpDeghost {
             UpDeghost::Gnd => 3,
         }
     }
+
+    pub const fn from_register_value(value: u8) -> Self {
+        match value & 0b11 {
+            0 => UpDeghost::VledMinus2V,
+            1 => UpDeghost::VledMinus2_5V,
+            2 => UpDeghost::VledMinus3V,
+            _ => UpDeghost::Gnd,
+        }
+    }
+}
+
+/// Low brightness compensation clock shift setting of a color group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LowBrightnessCompensation {
+    Off,
+    Mode1,
+    Mode2,
+    Mode3,
+}
+
+impl LowBrightnessCompensation {
+    pub const fn register_value(&self) -> u8 {
+        match self {
+            LowBrightnessCompensation::Off => 0,
+            LowBrightnessCompensation::Mode1 => 1,
+            LowBrightnessCompensation::Mode2 => 2,
+            LowBrightnessCompensation::Mode3 => 3,
+        }
+    }
+
+    pub const fn from_register_value(value: u8) -> Self {
+        match value & 0b11 {
+            0 => LowBrightnessCompensation::Off,
+            1 => LowBrightnessCompensation::Mode1,
+            2 => LowBrightnessCompensation::Mode2,
+            _ => LowBrightnessCompensation::Mode3,
+        }
+    }
 }
 
 /// Data refresh mode selection
-#[derive(Debug)]
+///
+/// In Mode 2 and Mode 3 written PWM data only takes effect on the next VSYNC pulse.
+/// The register map has no software update trigger, so these modes require the VSYNC
+/// pin to be driven (by a GPIO or a free running timer output).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataRefMode {
     /// 8 bit PWM, update instantly, no external VSYNC
     Mode1,
@@ -115,10 +306,19 @@ impl DataRefMode {
             DataRefMode::Mode3 => 2,
         }
     }
+
+    /// Both 2 and 3 select Mode 3, the reset value is 3.
+    pub const fn from_register_value(value: u8) -> Self {
+        match value & 0b11 {
+            0 => DataRefMode::Mode1,
+            1 => DataRefMode::Mode2,
+            _ => DataRefMode::Mode3,
+        }
+    }
 }
 
 /// Maximum current cetting
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CurrentSetting {
     Max3mA,
     Max5mA,
@@ -143,10 +343,37 @@ impl CurrentSetting {
             CurrentSetting::Max50mA => 7,
         }
     }
+
+    /// Maximum current per current sink in µA.
+    pub const fn max_current_ua(&self) -> u32 {
+        match self {
+            CurrentSetting::Max3mA => 3_000,
+            CurrentSetting::Max5mA => 5_000,
+            CurrentSetting::Max10mA => 10_000,
+            CurrentSetting::Max15mA => 15_000,
+            CurrentSetting::Max20mA => 20_000,
+            CurrentSetting::Max30mA => 30_000,
+            CurrentSetting::Max40mA => 40_000,
+            CurrentSetting::Max50mA => 50_000,
+        }
+    }
+
+    pub const fn from_register_value(value: u8) -> Self {
+        match value & 0b111 {
+            0 => CurrentSetting::Max3mA,
+            1 => CurrentSetting::Max5mA,
+            2 => CurrentSetting::Max10mA,
+            3 => CurrentSetting::Max15mA,
+            4 => CurrentSetting::Max20mA,
+            5 => CurrentSetting::Max30mA,
+            6 => CurrentSetting::Max40mA,
+            _ => CurrentSetting::Max50mA,
+        }
+    }
 }
 
 /// Fixed color groups for current sinks
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Group {
     /// CS0, CS3, CS6, CS9, CS12, CS15
     Group0,
@@ -175,7 +402,7 @@ impl Group {
 }
 
 /// Configurable group for each dot
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DotGroup {
     None,
     Group0,
@@ -194,7 +421,7 @@ impl DotGroup {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GlobalFaultState {
     led_open_detected: bool,
     led_short_detected: bool,
@@ -227,6 +454,15 @@ impl GlobalFaultState {
     }
 }
 
+/// Kind of fault detected on a single dot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    /// LED open detected (LOD)
+    Open,
+    /// LED short detected (LSD)
+    Short,
+}
+
 /// Represents a safe way to address a dot in the matrix.
 pub struct Dot<DV>(u16, core::marker::PhantomData<DV>);
 
@@ -234,11 +470,31 @@ impl<DV: DeviceVariant> Dot<DV> {
     /// Create [`Dot`] at `index`. Panics if given `index` is outside the device
     /// variants capabilites.
     pub fn with_index(index: u16) -> Self {
-        if index / 18 > DV::NUM_LINES as u16 {
-            panic!("Device variant does not support dot {index}");
+        match Self::try_with_index(index) {
+            Some(dot) => dot,
+            None => panic!("Device variant does not support dot {index}"),
         }
+    }
+
+    /// Create [`Dot`] at `index`, or `None` if given `index` is outside the device
+    /// variants capabilites.
+    pub fn try_with_index(index: u16) -> Option<Self> {
+        (index < DV::NUM_DOTS).then_some(Self(index, core::marker::PhantomData))
+    }
+
+    /// Create [`Dot`] at `line` and current sink `cs`, or `None` if either is outside
+    /// the device variants capabilities.
+    pub fn from_line_cs(line: u8, cs: u8) -> Option<Self> {
+        if line >= DV::NUM_LINES || cs >= DV::NUM_CURRENT_SINKS {
+            return None;
+        }
+
+        Self::try_with_index(line as u16 * DV::NUM_CURRENT_SINKS as u16 + cs as u16)
+    }
 
-        Self(index, core::marker::PhantomData)
+    /// Iterate over all dots of the device variant, starting at L0-CS0.
+    pub fn iter_all() -> impl Iterator<Item = Self> {
+        (0..DV::NUM_DOTS).map(|index| Self(index, core::marker::PhantomData))
     }
 
     pub fn index(&self) -> u16 {
@@ -270,6 +526,38 @@ pub trait DeviceVariant: seal::Sealed {
     const NUM_DOTS: u16 = Self::NUM_LINES as u16 * Self::NUM_CURRENT_SINKS as u16;
 }
 
+/// Number of lines the internal buffers are sized for. Defaults to the LP5860, the
+/// `max-dots-*` features shrink the buffers for applications using smaller variants
+/// only. With several of these features enabled, the largest one wins.
+#[cfg(not(any(
+    feature = "max-dots-18",
+    feature = "max-dots-36",
+    feature = "max-dots-72",
+    feature = "max-dots-144"
+)))]
+pub(crate) const MAX_LINES: usize = 11;
+#[cfg(feature = "max-dots-144")]
+pub(crate) const MAX_LINES: usize = 8;
+#[cfg(all(feature = "max-dots-72", not(feature = "max-dots-144")))]
+pub(crate) const MAX_LINES: usize = 4;
+#[cfg(all(
+    feature = "max-dots-36",
+    not(any(feature = "max-dots-72", feature = "max-dots-144"))
+))]
+pub(crate) const MAX_LINES: usize = 2;
+#[cfg(all(
+    feature = "max-dots-18",
+    not(any(
+        feature = "max-dots-36",
+        feature = "max-dots-72",
+        feature = "max-dots-144"
+    ))
+))]
+pub(crate) const MAX_LINES: usize = 1;
+
+/// Number of dots the internal buffers are sized for, see [`MAX_LINES`].
+pub(crate) const MAX_DOTS: usize = MAX_LINES * 18;
+
 #[doc(hidden)]
 pub struct Variant0;
 impl DeviceVariant for Variant0 {
@@ -311,17 +599,36 @@ pub struct DataModeUnconfigured;
 impl DataModeMarker for DataModeUnconfigured {}
 impl seal::Sealed for DataModeUnconfigured {}
 
+/// Data mode with PWM access, defining the PWM register layout.
+pub trait PwmDataMode: DataModeMarker {
+    /// Number of PWM registers per dot
+    const REGISTERS_PER_DOT: u16;
+}
+
 pub struct DataMode8Bit;
 impl DataModeMarker for DataMode8Bit {}
+impl PwmDataMode for DataMode8Bit {
+    const REGISTERS_PER_DOT: u16 = 1;
+}
 impl seal::Sealed for DataMode8Bit {}
 
 pub struct DataMode16Bit;
 impl DataModeMarker for DataMode16Bit {}
+impl PwmDataMode for DataMode16Bit {
+    const REGISTERS_PER_DOT: u16 = 2;
+}
 impl seal::Sealed for DataMode16Bit {}
 
 /// Generic driver for all LP586x variants.
 pub struct Lp586x<DV, I, DM> {
     interface: I,
+    overflow_behavior: OverflowBehavior,
+    flush_hooks: Option<FlushHooks>,
+    global_brightness: u8,
+    brightness_revision: u32,
+    brightness_target: u8,
+    brightness_slew_limit: Option<u8>,
+    low_pwm_policy: LowPwmPolicy,
     _data_mode: DM,
     _phantom_data: core::marker::PhantomData<DV>,
 }
@@ -351,6 +658,20 @@ where
     }
 }
 
+#[cfg(not(feature = "eh1_0"))]
+impl<DV: DeviceVariant, DM: DataModeMarker, IE, I2C> Lp586x<DV, interface::I2cInterface<I2C>, DM>
+where
+    I2C: embedded_hal::blocking::i2c::Write<Error = IE>
+        + embedded_hal::blocking::i2c::WriteRead<Error = IE>,
+{
+    pub fn new_with_i2c(
+        i2c: I2C,
+        address: u8,
+    ) -> Result<Lp586x<DV, interface::I2cInterface<I2C>, DataModeUnconfigured>, Error<IE>> {
+        Lp586x::<DV, _, DataModeUnconfigured>::new(interface::I2cInterface::new(i2c, address))
+    }
+}
+
 #[cfg(not(feature = "eh1_0"))]
 impl<DV: DeviceVariant, DM: DataModeMarker, SPI, CS, SPIE>
     Lp586x<DV, interface::SpiInterface<SPI, CS>, DM>
@@ -376,10 +697,12 @@ macro_rules! fault_per_dot_fn {
         pub fn $name(&mut self, dots: &mut [bool]) -> Result<(), Error<IE>> {
             let mut buffer = [0u8; 33];
 
-            self.interface.read_registers($reg, &mut buffer)?;
+            self.interface
+                .read_registers($reg, &mut buffer)
+                .map_err(|e| e.tagged(Op::Faults))?;
 
-            dots[..DV::NUM_DOTS as usize]
-                .iter_mut()
+            dots.iter_mut()
+                .take(DV::NUM_DOTS as usize)
                 .enumerate()
                 .map(|(i, dot)| {
                     (
@@ -397,26 +720,107 @@ macro_rules! fault_per_dot_fn {
     };
 }
 
-impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
-where
-    I: RegisterAccess<Error = Error<IE>>,
-    DM: DataModeMarker,
-{
+impl<DV, I, DM: PwmDataMode> Lp586x<DV, I, DM> {
+    /// First PWM register of `dot` in the data mode of this driver. In 16 bit mode
+    /// the low byte is at this register, the high byte at the next one.
+    pub const fn pwm_register_for(dot: u16) -> u16 {
+        Register::PWM_BRIGHTNESS_START + dot * DM::REGISTERS_PER_DOT
+    }
+
+    /// `value` as written to the PWM registers, raised according to the
+    /// [`LowPwmPolicy`] in 16 bit mode
+    pub(crate) fn apply_low_pwm_policy(&self, value: u16) -> u16 {
+        match self.low_pwm_policy {
+            LowPwmPolicy::Clamp if DM::REGISTERS_PER_DOT == 2 && value != 0 => {
+                value.max(Lp586x::<DV, I, DataMode16Bit>::FAULT_DETECTION_MIN_PWM)
+            }
+            _ => value,
+        }
+    }
+}
+
+impl<DV, I> Lp586x<DV, I, DataMode16Bit> {
+    /// Lowest 16 bit PWM value at which LED open and short detection is reliable
+    pub const FAULT_DETECTION_MIN_PWM: u16 = 6400;
+
+    /// Sets how PWM values below [`Self::FAULT_DETECTION_MIN_PWM`] are handled, e.g.
+    /// to keep fault detection working in dim scenes. See [`LowPwmPolicy`].
+    pub fn with_low_pwm_policy(mut self, low_pwm_policy: LowPwmPolicy) -> Self {
+        self.low_pwm_policy = low_pwm_policy;
+        self
+    }
+}
+
+impl<DV: DeviceVariant, I, DM: PwmDataMode> Lp586x<DV, I, DM> {
+    /// Plans the SPI writes of raw PWM register `data` (little endian in 16 bit mode)
+    /// starting at `start_dot`, for pushing frames with DMA. Returns `None` if the
+    /// data doesn't fit the device variant. See [`interface::plan_spi_writes`].
+    pub fn plan_pwm_writes(
+        start_dot: u16,
+        data: &[u8],
+        max_payload: usize,
+    ) -> Option<interface::SpiTransferPlan<'_>> {
+        let registers = DV::NUM_DOTS as usize * DM::REGISTERS_PER_DOT as usize;
+        let offset = start_dot as usize * DM::REGISTERS_PER_DOT as usize;
+
+        (offset + data.len() <= registers).then(|| {
+            interface::plan_spi_writes(Self::pwm_register_for(start_dot), data, max_payload)
+        })
+    }
+}
+
+/// Device dimensions, usable without a driver instance, e.g. for sizing buffers with
+/// `[u8; <Lp5868>::NUM_DOTS]`.
+impl<DV: DeviceVariant, I, DM> Lp586x<DV, I, DM> {
+    /// Number of lines (switches) of the device variant
+    pub const NUM_LINES: usize = DV::NUM_LINES as usize;
+
     /// Number of current sinks of the LP586x
     pub const NUM_CURRENT_SINKS: usize = DV::NUM_CURRENT_SINKS as usize;
 
     /// Total number of LEDs supported by this driver
     pub const NUM_DOTS: usize = DV::NUM_DOTS as usize;
 
+    /// Number of lines and current sinks of the device variant.
+    pub const fn dimensions() -> (usize, usize) {
+        (Self::NUM_LINES, Self::NUM_CURRENT_SINKS)
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Global brightness after a reset
+    pub const RESET_GLOBAL_BRIGHTNESS: u8 = 0xff;
+
     /// Time to wait after enabling the chip (t_chip_en)
     pub const T_CHIP_EN_US: u32 = 100;
 
+    /// Fails the build if the variant doesn't fit the buffers selected by the
+    /// `max-dots-*` features.
+    const FITS_BUFFERS: () = assert!(
+        DV::NUM_DOTS as usize <= MAX_DOTS,
+        "device variant exceeds the enabled max-dots-* feature"
+    );
+
     /// Create a new LP586x driver instance with the given `interface`.
     ///
     /// The returned driver has the chip enabled
     pub fn new(interface: I) -> Result<Lp586x<DV, I, DataModeUnconfigured>, Error<IE>> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS_BUFFERS;
+
         let mut driver = Lp586x {
             interface,
+            overflow_behavior: OverflowBehavior::default(),
+            flush_hooks: None,
+            global_brightness: Self::RESET_GLOBAL_BRIGHTNESS,
+            brightness_revision: 0,
+            brightness_target: Self::RESET_GLOBAL_BRIGHTNESS,
+            brightness_slew_limit: None,
+            low_pwm_policy: LowPwmPolicy::default(),
             _data_mode: DataModeUnconfigured,
             _phantom_data: core::marker::PhantomData,
         };
@@ -426,6 +830,64 @@ where
         Ok(driver)
     }
 
+    /// Sets how writes extending past the last dot are handled, see [`OverflowBehavior`].
+    pub fn with_overflow_behavior(mut self, overflow_behavior: OverflowBehavior) -> Self {
+        self.overflow_behavior = overflow_behavior;
+        self
+    }
+
+    /// Installs `flush_hooks`, called around every PWM write.
+    pub fn with_flush_hooks(mut self, flush_hooks: FlushHooks) -> Self {
+        self.flush_hooks = Some(flush_hooks);
+        self
+    }
+
+    /// Limits changes of the global brightness made with
+    /// [`Lp586x::set_target_brightness`] to `max_step` per PWM write, which smooths
+    /// e.g. ambient light driven brightness jumps over several frames.
+    pub fn with_brightness_slew_limit(mut self, max_step: u8) -> Self {
+        self.brightness_slew_limit = Some(max_step.max(1));
+        self
+    }
+
+    /// Writes raw PWM register `data` of `dots` dots starting at `start_dot`, calling
+    /// the [`FlushHooks`] if installed.
+    fn write_pwm_registers(
+        &mut self,
+        start_dot: u16,
+        dots: usize,
+        register: u16,
+        data: &[u8],
+    ) -> Result<(), Error<IE>> {
+        if let Some(hooks) = self.flush_hooks {
+            (hooks.on_flush_start)(start_dot, dots);
+        }
+
+        let result = self
+            .interface
+            .write_registers(register, data)
+            .map_err(|e| e.tagged(Op::WriteFrame));
+
+        if let Some(hooks) = self.flush_hooks {
+            (hooks.on_flush_end)(start_dot, dots);
+        }
+
+        result?;
+        self.step_brightness()
+    }
+
+    /// Number of values of a write of `len` values starting at `start_dot` to pass on
+    /// to the chip, according to the configured [`OverflowBehavior`].
+    fn dots_to_write(&self, start_dot: u16, len: usize) -> Result<usize, Error<IE>> {
+        let available = (DV::NUM_DOTS as usize).saturating_sub(start_dot as usize);
+
+        match self.overflow_behavior {
+            _ if len <= available => Ok(len),
+            OverflowBehavior::Error => Err(Error::DotOutOfRange),
+            OverflowBehavior::Clamp => Ok(available),
+        }
+    }
+
     /// Number of lines (switches) supported by this driver
     pub const fn num_lines(&self) -> u8 {
         DV::NUM_LINES
@@ -440,38 +902,118 @@ where
     ///
     /// After enabling the chip, wait t_chip_en (100µs) for the chip to enter normal mode.
     pub fn chip_enable(&mut self, enable: bool) -> Result<(), Error<IE>> {
-        self.interface.write_register(
-            Register::CHIP_EN,
-            if enable { BitFlags::CHIP_EN_CHIP_EN } else { 0 },
-        )
+        self.interface
+            .write_register(
+                Register::CHIP_EN,
+                if enable { BitFlags::CHIP_EN_CHIP_EN } else { 0 },
+            )
+            .map_err(|e| e.tagged(Op::ChipEnable))
     }
 
+    /// Writes `configuration` to the device configuration registers.
+    ///
+    /// Changing scan related settings (line count, PWM frequency, data refresh mode)
+    /// while the panel is lit can show a short glitch. Use
+    /// [`Lp586x::live_reconfigure`] on an active panel.
     pub fn configure(&mut self, configuration: &Configuration) -> Result<(), Error<IE>> {
-        self.interface.write_registers(
-            Register::DEV_INITIAL,
-            &[
-                configuration.dev_initial_reg_value(),
-                configuration.dev_config1_reg_value(),
-                configuration.dev_config2_reg_value(),
-                configuration.dev_config3_reg_value(),
-            ],
-        )?;
+        self.interface
+            .write_registers(Register::DEV_INITIAL, &configuration.register_values())
+            .map_err(|e| e.tagged(Op::Configure))?;
+
+        Ok(())
+    }
+
+    /// Changes the configuration of an active panel without visible transients: the
+    /// panel is blanked by zeroing the global brightness, reconfigured, and the
+    /// previous global brightness (see [`Lp586x::global_brightness`]) is restored.
+    pub fn live_reconfigure(&mut self, configuration: &Configuration) -> Result<(), Error<IE>> {
+        let brightness = self.global_brightness;
+        let target = self.brightness_target;
+
+        self.set_global_brightness(0)?;
+        self.configure(configuration)?;
+        self.set_global_brightness(brightness)?;
+        self.brightness_target = target;
 
         Ok(())
     }
 
+    /// Checks whether the chip went through a reset (power-on, brown-out or software)
+    /// since it was last enabled.
+    ///
+    /// The LP586x has no dedicated reset flag, but every reset clears the chip enable
+    /// bit. So this also reports `true` after the chip was deliberately disabled with
+    /// [`Lp586x::chip_enable`]. Enabling the chip clears the condition; use
+    /// [`Lp586x::verify_and_recover`] to also restore the configuration.
+    pub fn power_on_reset_detected(&mut self) -> Result<bool, Error<IE>> {
+        let chip_en = self
+            .interface
+            .read_register(Register::CHIP_EN)
+            .map_err(|e| e.tagged(Op::Configure))?;
+
+        Ok(chip_en & BitFlags::CHIP_EN_CHIP_EN == 0)
+    }
+
+    /// Verifies the chip is enabled and configured with `configuration`, otherwise
+    /// re-initializes it (reset, enable and configure). Returns `true` if the chip had
+    /// to be re-initialized, in which case all dot settings and PWM values are lost.
+    pub fn verify_and_recover(&mut self, configuration: &Configuration) -> Result<bool, Error<IE>> {
+        let mut registers = [0u8; 5];
+        self.interface
+            .read_registers(Register::CHIP_EN, &mut registers)
+            .map_err(|e| e.tagged(Op::Configure))?;
+
+        if registers[0] & BitFlags::CHIP_EN_CHIP_EN != 0
+            && registers[1..] == configuration.register_values()
+        {
+            return Ok(false);
+        }
+
+        self.reset()?;
+        self.chip_enable(true)?;
+        self.configure(configuration)?;
+
+        Ok(true)
+    }
+
+    /// Configures the chip with `configuration` adjusted for being filmed by a camera
+    /// (see [`Configuration::adjusted_for_camera`]).
+    ///
+    /// Returns the number of full scan refresh cycles per camera frame at `fps`. The
+    /// more cycles per frame (and per exposure), the less visible banding gets.
+    pub fn configure_for_camera(
+        &mut self,
+        configuration: Configuration,
+        fps: u32,
+    ) -> Result<u32, Error<IE>> {
+        let configuration = configuration.adjusted_for_camera();
+        self.configure(&configuration)?;
+
+        Ok(configuration.scan_refresh_hz() / fps.max(1))
+    }
+
     /// Resets the chip.
     pub fn reset(&mut self) -> Result<(), Error<IE>> {
-        self.interface.write_register(Register::RESET, 0xff)
+        self.interface
+            .write_register(Register::RESET, 0xff)
+            .map_err(|e| e.tagged(Op::Reset))?;
+        self.settle_brightness(Self::RESET_GLOBAL_BRIGHTNESS);
+
+        Ok(())
     }
 
-    /// Configures dot groups, starting at dot L0-CS0. At least the first dot group has
-    /// to be specified, and at most `self.num_dots()`.
+    /// Configures dot groups, starting at dot L0-CS0. Fails with
+    /// [`Error::DotOutOfRange`] for more than `self.num_dots()` dot groups, writes
+    /// nothing for none.
     pub fn set_dot_groups(&mut self, dot_groups: &[DotGroup]) -> Result<(), Error<IE>> {
-        let mut buffer = [0u8; 54];
+        let mut buffer = [0u8; 55];
 
-        assert!(dot_groups.len() <= self.num_dots() as usize);
-        assert!(!dot_groups.is_empty());
+        if dot_groups.len() > self.num_dots() as usize {
+            return Err(Error::DotOutOfRange);
+        }
+        if dot_groups.is_empty() {
+            return Ok(());
+        }
 
         dot_groups
             .iter()
@@ -491,36 +1033,239 @@ where
             + (dot_groups.len() - 1) % Self::NUM_CURRENT_SINKS / 4;
 
         self.interface
-            .write_registers(Register::DOT_GROUP_SELECT_START, &buffer[..=last_group])?;
+            .write_registers(Register::DOT_GROUP_SELECT_START, &buffer[..=last_group])
+            .map_err(|e| e.tagged(Op::DotSettings))?;
 
         Ok(())
     }
 
+    /// Assigns a single dot to `dot_group`, using a read-modify-write of the register
+    /// containing its group setting. Other dots keep their assignment.
+    pub fn set_dot_group(&mut self, dot: u16, dot_group: DotGroup) -> Result<(), Error<IE>> {
+        let dot = Dot::<DV>::try_with_index(dot).ok_or(Error::DotOutOfRange)?;
+        let register = Register::dot_group_select(dot.line() * 5 + dot.current_sink() / 4);
+        let shift = dot.current_sink() % 4 * 2;
+
+        let value = self
+            .interface
+            .read_register(register)
+            .map_err(|e| e.tagged(Op::DotSettings))?;
+        self.interface
+            .write_register(
+                register,
+                value & !(0b11 << shift) | dot_group.register_value() << shift,
+            )
+            .map_err(|e| e.tagged(Op::DotSettings))
+    }
+
     /// Set dot current, starting from `start_dot`.
     pub fn set_dot_current(&mut self, start_dot: u16, current: &[u8]) -> Result<(), Error<IE>> {
-        assert!(current.len() <= self.num_dots() as usize);
-        assert!(!current.is_empty());
+        let len = self.dots_to_write(start_dot, current.len())?;
+        if len == 0 {
+            return Ok(());
+        }
 
         self.interface
-            .write_registers(Register::DOT_CURRENT_START + start_dot, current)?;
+            .write_registers(Register::DOT_CURRENT_START + start_dot, &current[..len])
+            .map_err(|e| e.tagged(Op::DotSettings))?;
 
         Ok(())
     }
 
+    /// True, if current sink `cs` is enabled on every line, see
+    /// [`Lp586x::set_current_sink_enabled`].
+    pub fn current_sink_enabled(&mut self, cs: u8) -> Result<bool, Error<IE>> {
+        if cs >= DV::NUM_CURRENT_SINKS {
+            return Err(Error::DotOutOfRange);
+        }
+
+        let mut buffer = [0u8; 33];
+        let onoff = &mut buffer[..DV::NUM_LINES as usize * 3];
+        self.interface
+            .read_registers(Register::DOT_ONOFF_START, onoff)
+            .map_err(|e| e.tagged(Op::DotSettings))?;
+
+        Ok(onoff
+            .chunks(3)
+            .all(|line| line[(cs / 8) as usize] & (1 << (cs % 8)) > 0))
+    }
+
+    /// Enables or disables current sink `cs` on all lines, e.g. to blank columns that
+    /// are not populated and would otherwise show ghosting artifacts. The on/off
+    /// states of the other current sinks are kept.
+    pub fn set_current_sink_enabled(&mut self, cs: u8, enabled: bool) -> Result<(), Error<IE>> {
+        if cs >= DV::NUM_CURRENT_SINKS {
+            return Err(Error::DotOutOfRange);
+        }
+
+        self.update_dot_onoff(|_, line| {
+            let mask = 1 << (cs % 8);
+            if enabled {
+                line[(cs / 8) as usize] |= mask;
+            } else {
+                line[(cs / 8) as usize] &= !mask;
+            }
+        })
+    }
+
+    /// Enables or disables the current sinks starting at CS0 on all lines, in a single
+    /// read and write of the on/off registers. Current sinks past the end of `enabled`
+    /// keep their state, values past the last current sink are ignored.
+    pub fn set_current_sinks_enabled(&mut self, enabled: &[bool]) -> Result<(), Error<IE>> {
+        self.update_dot_onoff(|_, line| {
+            enabled
+                .iter()
+                .take(DV::NUM_CURRENT_SINKS as usize)
+                .enumerate()
+                .for_each(|(cs, &enabled)| {
+                    let mask = 1 << (cs % 8);
+                    if enabled {
+                        line[cs / 8] |= mask;
+                    } else {
+                        line[cs / 8] &= !mask;
+                    }
+                })
+        })
+    }
+
+    /// True, if any dot of `line` is enabled, see [`Lp586x::set_line_enabled`].
+    pub fn line_enabled(&mut self, line: u8) -> Result<bool, Error<IE>> {
+        if line >= DV::NUM_LINES {
+            return Err(Error::DotOutOfRange);
+        }
+
+        let mut onoff = [0u8; 3];
+        self.interface
+            .read_registers(Register::dot_onoff(line as u16 * 3), &mut onoff)
+            .map_err(|e| e.tagged(Op::DotSettings))?;
+
+        Ok(onoff.iter().any(|&value| value != 0))
+    }
+
+    /// Enables or disables all dots of `line`, e.g. for boards not populating every
+    /// line. Enabling a line turns on all of its current sinks, so disable blanked
+    /// current sinks again with [`Lp586x::set_current_sink_enabled`].
+    ///
+    /// Disabled lines are still scanned. Trailing unused lines are better left out of
+    /// the scan with [`Configuration::with_max_line_num`], which shortens the frame.
+    pub fn set_line_enabled(&mut self, line: u8, enabled: bool) -> Result<(), Error<IE>> {
+        if line >= DV::NUM_LINES {
+            return Err(Error::DotOutOfRange);
+        }
+
+        let value = if enabled { 0xff } else { 0 };
+        self.interface
+            .write_registers(Register::dot_onoff(line as u16 * 3), &[value; 3])
+            .map_err(|e| e.tagged(Op::DotSettings))
+    }
+
+    /// Read-modify-write of the dot on/off registers, calling `update` with the line
+    /// number and the three on/off registers of every line.
+    fn update_dot_onoff(&mut self, mut update: impl FnMut(u8, &mut [u8])) -> Result<(), Error<IE>> {
+        let mut buffer = [0u8; 33];
+        let onoff = &mut buffer[..DV::NUM_LINES as usize * 3];
+        self.interface
+            .read_registers(Register::DOT_ONOFF_START, onoff)
+            .map_err(|e| e.tagged(Op::DotSettings))?;
+
+        onoff
+            .chunks_mut(3)
+            .enumerate()
+            .for_each(|(line, registers)| update(line as u8, registers));
+
+        self.interface
+            .write_registers(Register::DOT_ONOFF_START, onoff)
+            .map_err(|e| e.tagged(Op::DotSettings))
+    }
+
     /// Sets the global brightness across all LEDs.
     pub fn set_global_brightness(&mut self, brightness: u8) -> Result<(), Error<IE>> {
         self.interface
-            .write_register(Register::GLOBAL_BRIGHTNESS, brightness)?;
+            .write_register(Register::GLOBAL_BRIGHTNESS, brightness)
+            .map_err(|e| e.tagged(Op::Brightness))?;
+        self.settle_brightness(brightness);
+
+        Ok(())
+    }
+
+    /// Sets the global brightness to approach. Without a slew limit (see
+    /// [`Lp586x::with_brightness_slew_limit`]) it is written right away, otherwise
+    /// every PWM write moves the global brightness by at most the limit towards it.
+    pub fn set_target_brightness(&mut self, brightness: u8) -> Result<(), Error<IE>> {
+        self.brightness_target = brightness;
+
+        match self.brightness_slew_limit {
+            Some(_) => Ok(()),
+            None => self.set_global_brightness(brightness),
+        }
+    }
+
+    /// Global brightness being approached, see [`Lp586x::set_target_brightness`].
+    pub fn brightness_target(&self) -> u8 {
+        self.brightness_target
+    }
+
+    /// Moves the global brightness one step towards the target brightness, as done
+    /// after every PWM write. Useful for ramping while no frames are written.
+    pub fn step_brightness(&mut self) -> Result<(), Error<IE>> {
+        let (current, target) = (self.global_brightness, self.brightness_target);
+        if current == target {
+            return Ok(());
+        }
+
+        let max_step = self.brightness_slew_limit.unwrap_or(u8::MAX);
+        let brightness = if target > current {
+            current.saturating_add(max_step).min(target)
+        } else {
+            current.saturating_sub(max_step).max(target)
+        };
+
+        self.interface
+            .write_register(Register::GLOBAL_BRIGHTNESS, brightness)
+            .map_err(|e| e.tagged(Op::Brightness))?;
+        self.update_brightness_cache(brightness);
 
         Ok(())
     }
 
+    /// Global brightness last written through this driver, without a bus read.
+    pub fn global_brightness(&self) -> u8 {
+        self.global_brightness
+    }
+
+    /// Counter incremented on every change of [`Lp586x::global_brightness`]. Store it
+    /// and pass it to [`Lp586x::brightness_changed_since`] later on.
+    pub fn brightness_revision(&self) -> u32 {
+        self.brightness_revision
+    }
+
+    /// True, if the global brightness changed since `revision` was obtained from
+    /// [`Lp586x::brightness_revision`].
+    pub fn brightness_changed_since(&self, revision: u32) -> bool {
+        self.brightness_revision != revision
+    }
+
+    /// Records `brightness` as written and as the target, so the slew limit does not
+    /// move away from it.
+    pub(crate) fn settle_brightness(&mut self, brightness: u8) {
+        self.update_brightness_cache(brightness);
+        self.brightness_target = brightness;
+    }
+
+    pub(crate) fn update_brightness_cache(&mut self, brightness: u8) {
+        if brightness != self.global_brightness {
+            self.global_brightness = brightness;
+            self.brightness_revision = self.brightness_revision.wrapping_add(1);
+        }
+    }
+
     /// Sets the brightness across all LEDs in the given [`Group`].
     /// Note that individual LEDS/dots need to be assigned to a `LED_DOT_GROUP`
     /// for this setting to have effect. By default dots ar not assigned to any group.
     pub fn set_group_brightness(&mut self, group: Group, brightness: u8) -> Result<(), Error<IE>> {
         self.interface
-            .write_register(group.brightness_reg_addr(), brightness)?;
+            .write_register(group.brightness_reg_addr(), brightness)
+            .map_err(|e| e.tagged(Op::Brightness))?;
 
         Ok(())
     }
@@ -528,54 +1273,205 @@ where
     /// Set group current scaling (0..127).
     pub fn set_group_current(&mut self, group: Group, current: u8) -> Result<(), Error<IE>> {
         self.interface
-            .write_register(group.current_reg_addr(), current.min(0x7f))?;
+            .write_register(group.current_reg_addr(), current.min(0x7f))
+            .map_err(|e| e.tagged(Op::Brightness))?;
+
+        Ok(())
+    }
+
+    /// Sets the brightness of all three [`Group`]s in a single transaction, in order
+    /// group 0, group 1, group 2.
+    pub fn set_all_group_brightness(&mut self, brightness: [u8; 3]) -> Result<(), Error<IE>> {
+        self.interface
+            .write_registers(Register::GROUP0_BRIGHTNESS, &brightness)
+            .map_err(|e| e.tagged(Op::Brightness))?;
+
+        Ok(())
+    }
+
+    /// Sets the current scaling (0..127) of all three [`Group`]s in a single
+    /// transaction, in order group 0, group 1, group 2.
+    pub fn set_all_group_currents(&mut self, currents: [u8; 3]) -> Result<(), Error<IE>> {
+        self.interface
+            .write_registers(Register::GROUP0_CURRENT, &currents.map(|c| c.min(0x7f)))
+            .map_err(|e| e.tagged(Op::Brightness))?;
 
         Ok(())
     }
 
     /// Get global fault state, indicating if any LEDs in the matrix have a
     /// open or short failure.
+    ///
+    /// LED open and short detection is always active, the register map has no bits to
+    /// turn it off. Dots need a PWM value of at least 25 (8 bit) or 6400 (16 bit) for
+    /// reliable detection, see the datasheet. The `lod_removal` and `lsd_removal`
+    /// options of [`configuration::Configuration`] only control ghosting removal.
     pub fn get_global_fault_state(&mut self) -> Result<GlobalFaultState, Error<IE>> {
-        let fault_state_value = self.interface.read_register(Register::FAULT_STATE)?;
+        let fault_state_value = self
+            .interface
+            .read_register(Register::FAULT_STATE)
+            .map_err(|e| e.tagged(Op::Faults))?;
         Ok(GlobalFaultState::from_reg_value(fault_state_value))
     }
 
     fault_per_dot_fn!(
         get_led_open_states,
         Register::DOT_LOD_START,
-        "Get LED open states, starting from the first dot."
+        "Get LED open states, starting from the first dot. Fills at most `dots.len()` states."
     );
 
     fault_per_dot_fn!(
         get_led_short_states,
         Register::DOT_LSD_START,
-        "Get LED short states, starting from the first dot."
+        "Get LED short states, starting from the first dot. Fills at most `dots.len()` states."
     );
 
     /// Clear all led open detection (LOD) indication bits
     pub fn clear_led_open_fault(&mut self) -> Result<(), Error<IE>> {
-        self.interface.write_register(Register::LOD_CLEAR, 0xF)
+        self.interface
+            .write_register(Register::LOD_CLEAR, 0xF)
+            .map_err(|e| e.tagged(Op::Faults))
     }
 
     /// Clear all led short detection (LSD) indication bits
     pub fn clear_led_short_fault(&mut self) -> Result<(), Error<IE>> {
-        self.interface.write_register(Register::LSD_CLEAR, 0xF)
+        self.interface
+            .write_register(Register::LSD_CLEAR, 0xF)
+            .map_err(|e| e.tagged(Op::Faults))
     }
 
-    pub fn into_16bit_data_mode(self) -> Result<Lp586x<DV, I, DataMode16Bit>, Error<IE>> {
-        Ok(Lp586x {
-            interface: self.interface,
-            _data_mode: DataMode16Bit,
-            _phantom_data: core::marker::PhantomData,
-        })
-    }
+    /// Reads the LED open and short states line by line and calls `on_fault` for every
+    /// faulty dot, without buffering the states of the whole matrix. Open faults of a
+    /// line are reported before its short faults.
+    pub fn stream_fault_states(
+        &mut self,
+        mut on_fault: impl FnMut(Dot<DV>, FaultKind),
+    ) -> Result<(), Error<IE>> {
+        for line in 0..DV::NUM_LINES {
+            for (start_register, kind) in [
+                (Register::DOT_LOD_START, FaultKind::Open),
+                (Register::DOT_LSD_START, FaultKind::Short),
+            ] {
+                let mut states = [0u8; 3];
+                self.interface
+                    .read_registers(start_register + line as u16 * 3, &mut states)
+                    .map_err(|e| e.tagged(Op::Faults))?;
+
+                (0..DV::NUM_CURRENT_SINKS)
+                    .filter(|cs| states[(cs / 8) as usize] & (1 << (cs % 8)) > 0)
+                    .filter_map(|cs| Dot::from_line_cs(line, cs))
+                    .for_each(|dot| on_fault(dot, kind));
+            }
+        }
 
-    pub fn into_8bit_data_mode(self) -> Result<Lp586x<DV, I, DataMode8Bit>, Error<IE>> {
-        Ok(Lp586x {
-            interface: self.interface,
-            _data_mode: DataMode8Bit,
-            _phantom_data: core::marker::PhantomData,
-        })
+        Ok(())
+    }
+
+    /// Get the LED open state of the dot at `line` and current sink `cs`.
+    pub fn led_open_at(&mut self, line: u8, cs: u8) -> Result<bool, Error<IE>> {
+        let dot = Dot::<DV>::from_line_cs(line, cs).ok_or(Error::DotOutOfRange)?;
+        let value = self
+            .interface
+            .read_register(Register::dot_lod(dot.line() * 3 + dot.current_sink() / 8))
+            .map_err(|e| e.tagged(Op::Faults))?;
+
+        Ok(value & (1 << (dot.current_sink() % 8)) > 0)
+    }
+
+    /// Get the LED short state of the dot at `line` and current sink `cs`.
+    pub fn led_short_at(&mut self, line: u8, cs: u8) -> Result<bool, Error<IE>> {
+        let dot = Dot::<DV>::from_line_cs(line, cs).ok_or(Error::DotOutOfRange)?;
+        let value = self
+            .interface
+            .read_register(Register::dot_lsd(dot.line() * 3 + dot.current_sink() / 8))
+            .map_err(|e| e.tagged(Op::Faults))?;
+
+        Ok(value & (1 << (dot.current_sink() % 8)) > 0)
+    }
+
+    /// Set the PWM value of the dot at `line` and current sink `cs`.
+    pub fn set_pwm_at<T>(&mut self, line: u8, cs: u8, value: T) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let dot = Dot::<DV>::from_line_cs(line, cs).ok_or(Error::DotOutOfRange)?;
+        self.set_pwm(dot.index(), &[value])
+    }
+
+    /// Get the PWM value of the dot at `line` and current sink `cs`.
+    pub fn get_pwm_at<T>(&mut self, line: u8, cs: u8) -> Result<T, Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let dot = Dot::<DV>::from_line_cs(line, cs).ok_or(Error::DotOutOfRange)?;
+        self.get_pwm(dot.index())
+    }
+
+    /// Set PWM values of arbitrary dots, given as `(dot, value)` pairs.
+    ///
+    /// The pairs are sorted by dot in place and runs of consecutive dots are written
+    /// in a single burst, which is cheaper than a full frame write when only a few dots
+    /// change. If a dot is given more than once, the order of the writes is unspecified.
+    pub fn set_pixels<T: Copy + Default>(
+        &mut self,
+        pixels: &mut [(u16, T)],
+    ) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let mut values = [T::default(); MAX_DOTS];
+
+        pixels.sort_unstable_by_key(|(dot, _)| *dot);
+
+        let mut remaining = &pixels[..];
+        while let Some(&(start_dot, _)) = remaining.first() {
+            let run_len = remaining
+                .iter()
+                .take(values.len())
+                .enumerate()
+                .take_while(|(i, (dot, _))| start_dot.checked_add(*i as u16) == Some(*dot))
+                .count();
+
+            values
+                .iter_mut()
+                .zip(&remaining[..run_len])
+                .for_each(|(value, (_, pixel))| *value = *pixel);
+
+            self.set_pwm(start_dot, &values[..run_len])?;
+            remaining = &remaining[run_len..];
+        }
+
+        Ok(())
+    }
+
+    pub fn into_16bit_data_mode(self) -> Result<Lp586x<DV, I, DataMode16Bit>, Error<IE>> {
+        Ok(Lp586x {
+            interface: self.interface,
+            overflow_behavior: self.overflow_behavior,
+            flush_hooks: self.flush_hooks,
+            global_brightness: self.global_brightness,
+            brightness_revision: self.brightness_revision,
+            brightness_target: self.brightness_target,
+            brightness_slew_limit: self.brightness_slew_limit,
+            low_pwm_policy: self.low_pwm_policy,
+            _data_mode: DataMode16Bit,
+            _phantom_data: core::marker::PhantomData,
+        })
+    }
+
+    pub fn into_8bit_data_mode(self) -> Result<Lp586x<DV, I, DataMode8Bit>, Error<IE>> {
+        Ok(Lp586x {
+            interface: self.interface,
+            overflow_behavior: self.overflow_behavior,
+            flush_hooks: self.flush_hooks,
+            global_brightness: self.global_brightness,
+            brightness_revision: self.brightness_revision,
+            brightness_target: self.brightness_target,
+            brightness_slew_limit: self.brightness_slew_limit,
+            low_pwm_policy: self.low_pwm_policy,
+            _data_mode: DataMode8Bit,
+            _phantom_data: core::marker::PhantomData,
+        })
     }
 }
 
@@ -583,7 +1479,8 @@ where
 pub trait PwmAccess<T> {
     type Error;
 
-    /// Set PWM values of `values.len()` dots, starting from dot `start`.
+    /// Set PWM values of `values.len()` dots, starting from dot `start`. Values past
+    /// the last dot are handled according to the drivers [`OverflowBehavior`].
     fn set_pwm(&mut self, start: u16, values: &[T]) -> Result<(), Self::Error>;
 
     /// Get PWM value of a single dot.
@@ -597,20 +1494,27 @@ where
     type Error = Error<IE>;
 
     fn set_pwm(&mut self, start_dot: u16, values: &[u8]) -> Result<(), Self::Error> {
-        if values.len() + start_dot as usize > (DV::NUM_DOTS as usize) {
-            // TODO: probably we don't want to panic in an embedded system...
-            panic!("Too many values supplied for given start and device variant.");
+        let values = &values[..self.dots_to_write(start_dot, values.len())?];
+        if values.is_empty() {
+            return Ok(());
         }
 
-        self.interface
-            .write_registers(Register::PWM_BRIGHTNESS_START + start_dot, values)?;
-
-        Ok(())
+        self.write_pwm_registers(
+            start_dot,
+            values.len(),
+            Self::pwm_register_for(start_dot),
+            values,
+        )
     }
 
     fn get_pwm(&mut self, dot: u16) -> Result<u8, Self::Error> {
+        if dot >= DV::NUM_DOTS {
+            return Err(Error::DotOutOfRange);
+        }
+
         self.interface
-            .read_register(Register::PWM_BRIGHTNESS_START + dot)
+            .read_register(Self::pwm_register_for(dot))
+            .map_err(|e| e.tagged(Op::ReadFrame))
     }
 }
 
@@ -621,49 +1525,134 @@ where
     type Error = Error<IE>;
 
     fn set_pwm(&mut self, start_dot: u16, values: &[u16]) -> Result<(), Self::Error> {
-        let mut buffer = [0; Variant0::NUM_DOTS as usize * 2];
+        let mut buffer = [0; MAX_DOTS * 2];
 
-        if values.len() + start_dot as usize > (DV::NUM_DOTS as usize) {
-            // TODO: probably we don't want to panic in an embedded system...
-            panic!("Too many values supplied for given start and device variant.");
+        let values = &values[..self.dots_to_write(start_dot, values.len())?];
+        if values.is_empty() {
+            return Ok(());
         }
 
         // map u16 values to a u8 buffer (little endian)
-        values.iter().enumerate().for_each(|(idx, value)| {
+        values.iter().enumerate().for_each(|(idx, &value)| {
+            let value = self.apply_low_pwm_policy(value);
             let register_offset = idx * 2;
             [buffer[register_offset], buffer[register_offset + 1]] = value.to_le_bytes();
         });
 
-        self.interface.write_registers(
-            Register::PWM_BRIGHTNESS_START + start_dot * 2,
+        self.write_pwm_registers(
+            start_dot,
+            values.len(),
+            Self::pwm_register_for(start_dot),
             &buffer[..values.len() * 2],
-        )?;
-
-        Ok(())
+        )
     }
 
     fn get_pwm(&mut self, dot: u16) -> Result<u16, Self::Error> {
+        if dot >= DV::NUM_DOTS {
+            return Err(Error::DotOutOfRange);
+        }
+
         self.interface
-            .read_register_wide(Register::PWM_BRIGHTNESS_START + (dot * 2))
+            .read_register_wide(Self::pwm_register_for(dot))
+            .map_err(|e| e.tagged(Op::ReadFrame))
+    }
+}
+
+impl<DV: DeviceVariant, I, IE> Lp586x<DV, I, DataMode8Bit>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Sets 16 bit PWM `values` starting at `start_dot`, keeping the upper byte of
+    /// each, so rendering code producing 16 bit values works in either data mode.
+    pub fn set_pwm_u16_truncated(
+        &mut self,
+        start_dot: u16,
+        values: &[u16],
+    ) -> Result<(), Error<IE>> {
+        let mut buffer = [0u8; MAX_DOTS];
+        let len = self.dots_to_write(start_dot, values.len())?;
+
+        buffer
+            .iter_mut()
+            .zip(&values[..len])
+            .for_each(|(pwm, value)| *pwm = (value >> 8) as u8);
+
+        self.set_pwm(start_dot, &buffer[..len])
+    }
+}
+
+impl<DV: DeviceVariant, I, IE> Lp586x<DV, I, DataMode16Bit>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Sets 8 bit PWM `values` starting at `start_dot`, expanded to full scale 16 bit
+    /// (`0xff` becomes `0xffff`), so rendering code producing 8 bit values works in
+    /// either data mode.
+    pub fn set_pwm_u8_scaled(&mut self, start_dot: u16, values: &[u8]) -> Result<(), Error<IE>> {
+        let mut buffer = [0u16; MAX_DOTS];
+        let len = self.dots_to_write(start_dot, values.len())?;
+
+        buffer
+            .iter_mut()
+            .zip(&values[..len])
+            .for_each(|(pwm, &value)| *pwm = value as u16 * 0x101);
+
+        self.set_pwm(start_dot, &buffer[..len])
     }
 }
 
 #[cfg(feature = "eh1_0")]
-impl<DV, SPID: eh1_0::spi::SpiDevice, DM> Lp586x<DV, interface::SpiDeviceInterface<SPID>, DM> {
+impl<DV, SPID: eh1_0::spi::SpiDevice, DM, const MAX_TRANSFER_SIZE: usize>
+    Lp586x<DV, interface::SpiDeviceInterface<SPID, MAX_TRANSFER_SIZE>, DM>
+{
     /// Destroys the driver and releases the owned [`SpiDevice`].
     pub fn release(self) -> SPID {
         self.interface.release()
     }
 }
 
-#[cfg(feature = "eh1_0")]
-impl<DV, I2C: eh1_0::i2c::I2c, DM> Lp586x<DV, interface::I2cInterface<I2C>, DM> {
-    /// Destorys the driver and releases the owned [`I2c`].
+impl<DV, I2C, DM, const MAX_TRANSFER_SIZE: usize>
+    Lp586x<DV, interface::I2cInterface<I2C, MAX_TRANSFER_SIZE>, DM>
+{
+    /// Destroys the driver and releases the owned I2C bus.
+    pub fn release(self) -> I2C {
+        self.interface.release()
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<DV, I2C, DM, const MAX_TRANSFER_SIZE: usize>
+    Lp586x<DV, interface::I2cWriteStopReadInterface<I2C, MAX_TRANSFER_SIZE>, DM>
+{
+    /// Destroys the driver and releases the owned I2C bus.
     pub fn release(self) -> I2C {
         self.interface.release()
     }
 }
 
+#[cfg(not(feature = "eh1_0"))]
+impl<DV, I2C, DM> Lp586x<DV, interface::I2cWriteIterInterface<I2C>, DM> {
+    /// Destroys the driver and releases the owned I2C bus.
+    pub fn release(self) -> I2C {
+        self.interface.release()
+    }
+}
+
+#[cfg(not(feature = "eh1_0"))]
+impl<DV, I2C, DM> Lp586x<DV, interface::I2cTransactionalInterface<I2C>, DM> {
+    /// Destroys the driver and releases the owned I2C bus.
+    pub fn release(self) -> I2C {
+        self.interface.release()
+    }
+}
+
+impl<DV, SPI, CS, DM> Lp586x<DV, interface::SpiInterface<SPI, CS>, DM> {
+    /// Destroys the driver and releases the owned SPI bus and CS pin.
+    pub fn release(self) -> (SPI, CS) {
+        (self.interface.spi, self.interface.cs)
+    }
+}
+
 #[cfg(test)]
 impl<DV, DM> Lp586x<DV, interface::mock::MockInterface, DM> {
     /// Destroys the drivers and returns the owned [`MockInterface`].
@@ -673,19 +1662,19 @@ impl<DV, DM> Lp586x<DV, interface::mock::MockInterface, DM> {
 }
 
 /// LP5860 driver with 11 lines
-pub type Lp5860<I> = Lp586x<Variant0, I, DataModeUnconfigured>;
+pub type Lp5860<I = ()> = Lp586x<Variant0, I, DataModeUnconfigured>;
 
 /// LP5861 driver with 1 line
-pub type Lp5861<I> = Lp586x<Variant1, I, DataModeUnconfigured>;
+pub type Lp5861<I = ()> = Lp586x<Variant1, I, DataModeUnconfigured>;
 
 /// LP5862 driver with 2 lines
-pub type Lp5862<I> = Lp586x<Variant2, I, DataModeUnconfigured>;
+pub type Lp5862<I = ()> = Lp586x<Variant2, I, DataModeUnconfigured>;
 
 /// LP5864 driver with 4 lines
-pub type Lp5864<I> = Lp586x<Variant4, I, DataModeUnconfigured>;
+pub type Lp5864<I = ()> = Lp586x<Variant4, I, DataModeUnconfigured>;
 
 /// LP5868 driver with 8 lines
-pub type Lp5868<I> = Lp586x<Variant8, I, DataModeUnconfigured>;
+pub type Lp5868<I = ()> = Lp586x<Variant8, I, DataModeUnconfigured>;
 
 #[cfg(test)]
 mod tests {
@@ -704,6 +1693,606 @@ mod tests {
         ledmatrix.release().done();
     }
 
+    #[test]
+    fn test_borrowed_interface() {
+        let mut interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0x80),
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+        ]);
+
+        {
+            let mut ledmatrix = Lp5861::new(&mut interface).unwrap();
+            ledmatrix.set_global_brightness(0x80).unwrap();
+        }
+
+        let interface_dyn: &mut dyn RegisterAccess<Error = Error<()>> = &mut interface;
+        Lp5861::new(interface_dyn).unwrap();
+
+        interface.done();
+    }
+
+    #[test]
+    fn test_dot_bounds() {
+        assert!(Dot::<Variant1>::try_with_index(17).is_some());
+        assert!(Dot::<Variant1>::try_with_index(18).is_none());
+        assert!(Dot::<Variant0>::try_with_index(197).is_some());
+        assert!(Dot::<Variant0>::try_with_index(198).is_none());
+
+        let dot = Dot::<Variant2>::from_line_cs(1, 3).unwrap();
+        assert_eq!(dot.index(), 21);
+        assert_eq!(dot.line(), 1);
+        assert_eq!(dot.current_sink(), 3);
+        assert!(Dot::<Variant2>::from_line_cs(2, 0).is_none());
+        assert!(Dot::<Variant2>::from_line_cs(0, 18).is_none());
+
+        assert_eq!(Dot::<Variant4>::iter_all().count(), 72);
+        assert_eq!(Dot::<Variant4>::iter_all().last().unwrap().index(), 71);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dot_with_index_out_of_range() {
+        Dot::<Variant8>::with_index(144);
+    }
+
+    #[test]
+    fn test_line_cs_addressing() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x065 + 5, 0b0000_0001),
+            Access::ReadRegister(0x086 + 3, 0b0000_0000),
+            Access::WriteRegisters(0x200 + 2 * 21, vec![0x34, 0x12]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+
+        assert!(ledmatrix.led_open_at(1, 16).unwrap());
+        assert!(!ledmatrix.led_short_at(1, 0).unwrap());
+        ledmatrix.set_pwm_at(1, 3, 0x1234u16).unwrap();
+        assert!(matches!(
+            ledmatrix.set_pwm_at(2, 0, 0u16),
+            Err(Error::DotOutOfRange)
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_set_all_groups() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x006, vec![0x10, 0x20, 0x30]),
+            Access::WriteRegisters(0x009, vec![0x7f, 0x40, 0x00]),
+        ]);
+
+        let mut ledmatrix = Lp5864::new(interface).unwrap();
+
+        ledmatrix
+            .set_all_group_brightness([0x10, 0x20, 0x30])
+            .unwrap();
+        ledmatrix
+            .set_all_group_currents([0xff, 0x40, 0x00])
+            .unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_set_pixels() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 3, vec![3, 4, 5]),
+            Access::WriteRegisters(0x200 + 10, vec![10]),
+            Access::WriteRegisters(0x200 + 70, vec![70, 71]),
+        ]);
+
+        let mut ledmatrix = Lp5864::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        ledmatrix
+            .set_pixels(&mut [(71, 71u8), (4, 4), (10, 10), (3, 3), (70, 70), (5, 5)])
+            .unwrap();
+        assert!(matches!(
+            ledmatrix.set_pixels(&mut [(u16::MAX, 1u8), (u16::MAX, 2)]),
+            Err(Error::DotOutOfRange)
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_error_op_tag() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::FailWrite(0x200),
+            Access::FailWrite(0x001),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        assert!(matches!(
+            ledmatrix.set_pwm(0, &[0u8]),
+            Err(Error::Interface {
+                op: Op::WriteFrame,
+                ..
+            })
+        ));
+        assert!(matches!(
+            ledmatrix.configure(&Configuration::new::<Variant2>()),
+            Err(Error::Interface {
+                op: Op::Configure,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Error::interface(()).tagged(Op::Reset).tagged(Op::Configure),
+            Error::Interface { op: Op::Reset, .. }
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_set_pwm_converted() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 2, vec![0x12, 0xff]),
+        ]);
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        ledmatrix
+            .set_pwm_u16_truncated(2, &[0x1234, 0xffff])
+            .unwrap();
+        ledmatrix.release().done();
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 4, vec![0x12, 0x12, 0xff, 0xff]),
+        ]);
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+        ledmatrix.set_pwm_u8_scaled(2, &[0x12, 0xff]).unwrap();
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_low_pwm_policy() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![0x00, 0x00, 0x00, 0x19, 0x00, 0x20]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap()
+            .with_low_pwm_policy(LowPwmPolicy::Clamp);
+        ledmatrix.set_pwm(0, &[0u16, 1, 0x2000]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_plan_pwm_writes() {
+        type Pwm16Bit = Lp586x<Variant1, (), DataMode16Bit>;
+
+        let frame = [0u8; 36];
+        let mut plan = Pwm16Bit::plan_pwm_writes(0, &frame, 32).unwrap();
+        assert_eq!(plan.next().unwrap().payload.len(), 32);
+        assert_eq!(plan.next().unwrap().header, [0x88, 0x20]);
+        assert!(plan.next().is_none());
+
+        assert!(Pwm16Bit::plan_pwm_writes(1, &frame, 32).is_none());
+    }
+
+    #[test]
+    fn test_pwm_register_for() {
+        type Pwm8Bit = Lp586x<Variant0, (), DataMode8Bit>;
+        type Pwm16Bit = Lp586x<Variant0, (), DataMode16Bit>;
+
+        assert_eq!(Pwm8Bit::pwm_register_for(0), 0x200);
+        assert_eq!(Pwm8Bit::pwm_register_for(197), 0x2c5);
+        assert_eq!(Pwm16Bit::pwm_register_for(0), 0x200);
+        assert_eq!(Pwm16Bit::pwm_register_for(197), 0x38a);
+    }
+
+    #[test]
+    fn test_flush_hooks() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static STARTED: AtomicUsize = AtomicUsize::new(0);
+        static ENDED: AtomicUsize = AtomicUsize::new(0);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 4, vec![0x34, 0x12, 0x78, 0x56]),
+            Access::FailWrite(0x200),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap()
+            .with_flush_hooks(FlushHooks {
+                on_flush_start: |_, dots| {
+                    STARTED.fetch_add(dots, Ordering::Relaxed);
+                },
+                on_flush_end: |_, dots| {
+                    ENDED.fetch_add(dots, Ordering::Relaxed);
+                },
+            });
+
+        ledmatrix.set_pwm(2, &[0x1234u16, 0x5678]).unwrap();
+        assert!(ledmatrix.set_pwm(0, &[0u16]).is_err());
+        assert_eq!(STARTED.load(Ordering::Relaxed), 3);
+        assert_eq!(ENDED.load(Ordering::Relaxed), 3);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_global_brightness_cache() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0xff),
+            Access::WriteRegister(0x005, 0x20),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        let revision = ledmatrix.brightness_revision();
+        assert_eq!(ledmatrix.global_brightness(), 0xff);
+
+        ledmatrix.set_global_brightness(0xff).unwrap();
+        assert!(!ledmatrix.brightness_changed_since(revision));
+
+        ledmatrix.set_global_brightness(0x20).unwrap();
+        assert!(ledmatrix.brightness_changed_since(revision));
+        assert_eq!(ledmatrix.global_brightness(), 0x20);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_live_reconfigure() {
+        let configuration = Configuration::new::<Variant2>();
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0x80),
+            Access::WriteRegister(0x005, 0),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegister(0x005, 0x80),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+        ledmatrix.set_global_brightness(0x80).unwrap();
+        ledmatrix.live_reconfigure(&configuration).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let buffer = [0u8; <Lp5868>::NUM_DOTS];
+
+        assert_eq!(buffer.len(), 144);
+        assert_eq!(<Lp5860>::dimensions(), (11, 18));
+        assert_eq!(Lp5864::<MockInterface>::NUM_LINES, 4);
+    }
+
+    #[test]
+    fn test_stream_fault_states() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x065, vec![0b0000_0001, 0, 0b0000_0010]),
+            Access::ReadRegisters(0x086, vec![0, 0, 0]),
+            Access::ReadRegisters(0x065 + 3, vec![0, 0, 0]),
+            Access::ReadRegisters(0x086 + 3, vec![0, 0b1000_0000, 0]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+
+        let mut faults = Vec::new();
+        ledmatrix
+            .stream_fault_states(|dot, kind| faults.push((dot.index(), kind)))
+            .unwrap();
+        assert_eq!(
+            faults,
+            vec![
+                (0, FaultKind::Open),
+                (17, FaultKind::Open),
+                (18 + 15, FaultKind::Short)
+            ]
+        );
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_overflow_behavior() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 34, vec![1, 2]),
+            Access::WriteRegisters(0x100 + 35, vec![7]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        assert!(matches!(
+            ledmatrix.set_pwm(34, &[1u8, 2, 3]),
+            Err(Error::DotOutOfRange)
+        ));
+
+        let mut ledmatrix = ledmatrix.with_overflow_behavior(OverflowBehavior::Clamp);
+        ledmatrix.set_pwm(34, &[1u8, 2, 3]).unwrap();
+        ledmatrix.set_pwm(36, &[4u8]).unwrap();
+        ledmatrix.set_dot_current(35, &[7, 8]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_power_on_reset_detected() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x000, 1),
+            Access::ReadRegister(0x000, 0),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+
+        assert!(!ledmatrix.power_on_reset_detected().unwrap());
+        assert!(ledmatrix.power_on_reset_detected().unwrap());
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_set_dot_group() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x00c + 5 + 1, 0b1111_1111),
+            Access::WriteRegister(0x00c + 5 + 1, 0b1110_1111),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+
+        // L1-CS6
+        ledmatrix.set_dot_group(24, DotGroup::Group1).unwrap();
+        assert!(matches!(
+            ledmatrix.set_dot_group(36, DotGroup::None),
+            Err(Error::DotOutOfRange)
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_current_sink_enabled() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x043, vec![0xff; 6]),
+            Access::WriteRegisters(0x043, vec![0xff, 0xdf, 0xff, 0xff, 0xdf, 0xff]),
+            Access::ReadRegisters(0x043, vec![0xff, 0xdf, 0xff, 0xff, 0xdf, 0xff]),
+            Access::ReadRegisters(0x043, vec![0xff, 0xdf, 0xff, 0xff, 0xdf, 0xff]),
+            Access::WriteRegisters(0x043, vec![0xfe, 0xff, 0xfc, 0xfe, 0xff, 0xfc]),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface).unwrap();
+
+        ledmatrix.set_current_sink_enabled(13, false).unwrap();
+        assert!(!ledmatrix.current_sink_enabled(13).unwrap());
+        assert!(matches!(
+            ledmatrix.set_current_sink_enabled(18, false),
+            Err(Error::DotOutOfRange)
+        ));
+
+        let mut enabled = [true; 20];
+        enabled[0] = false;
+        enabled[16] = false;
+        enabled[17] = false;
+        enabled[18] = false;
+        ledmatrix.set_current_sinks_enabled(&enabled).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_line_enabled() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x043 + 3 * 3, vec![0, 0, 0]),
+            Access::ReadRegisters(0x043 + 3 * 3, vec![0, 0, 0]),
+            Access::WriteRegisters(0x043 + 3, vec![0xff, 0xff, 0xff]),
+            Access::ReadRegisters(0x043 + 3, vec![0xff, 0xdf, 0xff]),
+        ]);
+
+        let mut ledmatrix = Lp5864::new(interface).unwrap();
+
+        ledmatrix.set_line_enabled(3, false).unwrap();
+        assert!(!ledmatrix.line_enabled(3).unwrap());
+        ledmatrix.set_line_enabled(1, true).unwrap();
+        assert!(ledmatrix.line_enabled(1).unwrap());
+        assert!(matches!(
+            ledmatrix.set_line_enabled(4, false),
+            Err(Error::DotOutOfRange)
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_brightness_slew_limit() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![1]),
+            Access::WriteRegister(0x005, 0xff - 0x40),
+            Access::WriteRegisters(0x200, vec![2]),
+            Access::WriteRegister(0x005, 0xff - 0x80),
+            Access::WriteRegister(0x005, 0x70),
+            Access::WriteRegister(0x005, 0x20),
+            Access::WriteRegisters(0x200, vec![3]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .with_brightness_slew_limit(0x40)
+            .into_8bit_data_mode()
+            .unwrap();
+
+        ledmatrix.set_target_brightness(0x70).unwrap();
+        assert_eq!(ledmatrix.global_brightness(), 0xff);
+        ledmatrix.set_pwm(0, &[1u8]).unwrap();
+        ledmatrix.set_pwm(0, &[2u8]).unwrap();
+        ledmatrix.step_brightness().unwrap();
+        ledmatrix.step_brightness().unwrap();
+        assert_eq!(ledmatrix.global_brightness(), 0x70);
+
+        ledmatrix.set_global_brightness(0x20).unwrap();
+        ledmatrix.set_pwm(0, &[3u8]).unwrap();
+        assert_eq!(ledmatrix.brightness_target(), 0x20);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_brightness_target_after_recovery() {
+        let configuration = Configuration::new::<Variant1>();
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x000, vec![0; 5]),
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegisters(0x200, vec![1]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .with_brightness_slew_limit(0x40)
+            .into_8bit_data_mode()
+            .unwrap();
+
+        ledmatrix.set_target_brightness(0x40).unwrap();
+        assert!(ledmatrix.verify_and_recover(&configuration).unwrap());
+        assert_eq!(ledmatrix.brightness_target(), 0xff);
+        ledmatrix.set_pwm(0, &[1u8]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_brightness_target_after_live_reconfigure() {
+        let configuration = Configuration::new::<Variant1>();
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x005, 0x80),
+            Access::WriteRegister(0x005, 0),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegister(0x005, 0x80),
+            Access::WriteRegisters(0x200, vec![1]),
+            Access::WriteRegister(0x005, 0x70),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .with_brightness_slew_limit(0x10)
+            .into_8bit_data_mode()
+            .unwrap();
+
+        ledmatrix.set_global_brightness(0x80).unwrap();
+        ledmatrix.set_target_brightness(0x20).unwrap();
+        ledmatrix.live_reconfigure(&configuration).unwrap();
+        assert_eq!(ledmatrix.brightness_target(), 0x20);
+        ledmatrix.set_pwm(0, &[1u8]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_out_of_range_inputs() {
+        let mut lod = vec![0; 33];
+        lod[0] = 0b0000_0110;
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x065, lod),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        assert!(matches!(
+            ledmatrix.set_dot_groups(&[DotGroup::Group0; 37]),
+            Err(Error::DotOutOfRange)
+        ));
+        ledmatrix.set_dot_groups(&[]).unwrap();
+        ledmatrix.set_dot_current(0, &[]).unwrap();
+        assert!(matches!(
+            ledmatrix.get_pwm(u16::MAX),
+            Err(Error::DotOutOfRange)
+        ));
+
+        let mut open = [false; 2];
+        ledmatrix.get_led_open_states(&mut open).unwrap();
+        assert_eq!(open, [false, true]);
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_set_dot_groups_all_lines() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x00c, [0x55, 0x55, 0x55, 0x55, 0x05].repeat(11)),
+        ]);
+
+        let mut ledmatrix = Lp5860::new(interface).unwrap();
+        ledmatrix.set_dot_groups(&[DotGroup::Group0; 198]).unwrap();
+
+        ledmatrix.release().done();
+    }
+
     #[test]
     fn test_set_dot_groups() {
         #[rustfmt::skip]
@@ -0,0 +1,318 @@
+//! Chip emulation without hardware, for running and debugging application code on the
+//! desk and for simulation tests.
+
+use core::convert::Infallible;
+
+use embedded_hal::blocking::i2c;
+
+use crate::{
+    interface::{RegisterAccess, I2C_REGISTER_PAGE_SIZE, REGISTER_ADDRESS_SPACE},
+    register::{BitFlags, Register},
+    DataRefMode, DeviceVariant, Error, FaultKind, Lp586x, Variant0,
+};
+
+/// Number of per dot fault registers of each kind, three per line
+const FAULT_REGISTERS: usize = 3 * Variant0::NUM_LINES as usize;
+
+/// Registers not cleared by a reset, with their values after the reset
+const RESET_VALUES: [(u16, u8); 9] = [
+    (Register::DEV_INITIAL, 0x5e),
+    (Register::DEV_CONFIG3, 0x57),
+    (Register::GLOBAL_BRIGHTNESS, 0xff),
+    (Register::GROUP0_BRIGHTNESS, 0xff),
+    (Register::GROUP1_BRIGHTNESS, 0xff),
+    (Register::GROUP2_BRIGHTNESS, 0xff),
+    (Register::GROUP0_CURRENT, 0x40),
+    (Register::GROUP1_CURRENT, 0x40),
+    (Register::GROUP2_CURRENT, 0x40),
+];
+
+/// Interface keeping the register contents in RAM instead of talking to a chip.
+///
+/// Written values read back as written, except for the fault clear and reset
+/// registers, which act like on the chip. LED faults can be injected with
+/// [`LoopbackInterface::inject_fault`] to exercise fault handling.
+#[derive(Debug, Clone)]
+pub struct LoopbackInterface {
+    registers: [u8; REGISTER_ADDRESS_SPACE],
+}
+
+impl LoopbackInterface {
+    /// Create an interface with all registers at their reset values.
+    pub fn new() -> Self {
+        let mut registers = [0; REGISTER_ADDRESS_SPACE];
+        for (register, value) in RESET_VALUES {
+            registers[register as usize] = value;
+        }
+        let dot_onoff = Register::DOT_ONOFF_START as usize;
+        registers[dot_onoff..dot_onoff + FAULT_REGISTERS].fill(0xff);
+
+        Self { registers }
+    }
+
+    /// Current value of `register`.
+    pub fn register(&self, register: u16) -> u8 {
+        self.registers[register as usize]
+    }
+
+    /// Current values of all registers, indexed by address.
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    /// Reports a fault of `kind` on dot `dot` (`line * 18 + cs`), in the per dot fault
+    /// registers as well as the global fault state.
+    pub fn inject_fault(&mut self, dot: u16, kind: FaultKind) {
+        let (start_register, global_flag) = match kind {
+            FaultKind::Open => (Register::DOT_LOD_START, BitFlags::FAULT_STATE_GLOBAL_LOD),
+            FaultKind::Short => (Register::DOT_LSD_START, BitFlags::FAULT_STATE_GLOBAL_LSD),
+        };
+        let (line, cs) = (dot / 18, dot % 18);
+
+        self.registers[(start_register + line * 3 + cs / 8) as usize] |= 1 << (cs % 8);
+        self.registers[Register::FAULT_STATE as usize] |= global_flag;
+    }
+
+    fn clear_faults(&mut self, start_register: u16, global_flag: u8) {
+        let start = start_register as usize;
+        self.registers[start..start + FAULT_REGISTERS].fill(0);
+        self.registers[Register::FAULT_STATE as usize] &= !global_flag;
+    }
+
+    /// Applies the side effects of writing `value` to `register`.
+    fn write(&mut self, register: u16, value: u8) {
+        match register {
+            Register::LOD_CLEAR => {
+                self.clear_faults(Register::DOT_LOD_START, BitFlags::FAULT_STATE_GLOBAL_LOD)
+            }
+            Register::LSD_CLEAR => {
+                self.clear_faults(Register::DOT_LSD_START, BitFlags::FAULT_STATE_GLOBAL_LSD)
+            }
+            Register::RESET if value == 0xff => *self = Self::new(),
+            _ => self.registers[register as usize] = value,
+        }
+    }
+}
+
+impl Default for LoopbackInterface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterAccess for LoopbackInterface {
+    type Error = Error<Infallible>;
+
+    fn read_registers(&mut self, start_register: u16, data: &mut [u8]) -> Result<(), Self::Error> {
+        let start = start_register as usize;
+        let registers = self
+            .registers
+            .get(start..start + data.len())
+            .ok_or(Error::BufferOverrun)?;
+
+        data.copy_from_slice(registers);
+        Ok(())
+    }
+
+    fn write_registers(&mut self, start_register: u16, data: &[u8]) -> Result<(), Self::Error> {
+        if start_register as usize + data.len() > REGISTER_ADDRESS_SPACE {
+            return Err(Error::BufferOverrun);
+        }
+
+        for (register, &value) in (start_register..).zip(data) {
+            self.write(register, value);
+        }
+
+        Ok(())
+    }
+}
+
+/// Error of a [`VirtualLp586x`] bus transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtualBusError {
+    /// The transfer was addressed to a different device
+    Nack,
+}
+
+/// Model of an LP586x on an I2C bus, for testing interfaces and drivers against the
+/// register semantics of the chip rather than a list of expected accesses.
+///
+/// The two lowest bits of the 7 bit device address select the register page, the
+/// first byte written is the register address within the page and the address
+/// increments with every data byte. Register contents behave like on a
+/// [`LoopbackInterface`].
+#[derive(Debug, Clone)]
+pub struct VirtualLp586x {
+    chip: LoopbackInterface,
+    address: u8,
+    register: u16,
+}
+
+impl VirtualLp586x {
+    /// Create a chip answering at the 7 bit I2C `address` (and the following three
+    /// page addresses), with all registers at their reset values.
+    pub fn new(address: u8) -> Self {
+        Self {
+            chip: LoopbackInterface::new(),
+            address: address & !0b11,
+            register: 0,
+        }
+    }
+
+    /// Register contents of the chip, e.g. for injecting faults.
+    pub fn chip(&mut self) -> &mut LoopbackInterface {
+        &mut self.chip
+    }
+
+    /// PWM value of `dot` as the chip would use it: 16 bit (low byte first) in
+    /// [`DataRefMode::Mode3`], 8 bit in all other modes.
+    pub fn pwm(&self, dot: u16) -> u16 {
+        let data_ref_mode = (self.chip.register(Register::DEV_INITIAL)
+            >> BitFlags::DEV_INITIAL_DATA_REF_MODE_SHIFT)
+            & BitFlags::DEV_INITIAL_DATA_REF_MODE_MASK;
+
+        // both 0b10 and 0b11 select Mode 3
+        if data_ref_mode >= DataRefMode::Mode3.register_value() {
+            let register = Register::PWM_BRIGHTNESS_START + dot * 2;
+            u16::from_le_bytes([
+                self.chip.register(register),
+                self.chip.register(register + 1),
+            ])
+        } else {
+            self.chip.register(Register::PWM_BRIGHTNESS_START + dot) as u16
+        }
+    }
+
+    /// Selects the register page of `address` and returns the data bytes following
+    /// the register address byte in `bytes`.
+    fn select<'b>(&mut self, address: u8, bytes: &'b [u8]) -> Result<&'b [u8], VirtualBusError> {
+        if address & !0b11 != self.address {
+            return Err(VirtualBusError::Nack);
+        }
+
+        if let Some((&register, data)) = bytes.split_first() {
+            self.register =
+                (address & 0b11) as u16 * I2C_REGISTER_PAGE_SIZE as u16 + register as u16;
+            Ok(data)
+        } else {
+            Ok(&[])
+        }
+    }
+
+    fn next_register(&mut self) -> u16 {
+        let register = self.register;
+        self.register = (register + 1) % REGISTER_ADDRESS_SPACE as u16;
+        register
+    }
+}
+
+impl i2c::Write for VirtualLp586x {
+    type Error = VirtualBusError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        for &value in self.select(address, bytes)? {
+            let register = self.next_register();
+            self.chip.write(register, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl i2c::Read for VirtualLp586x {
+    type Error = VirtualBusError;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.select(address, &[])?;
+        for value in buffer {
+            let register = self.next_register();
+            *value = self.chip.register(register);
+        }
+
+        Ok(())
+    }
+}
+
+impl i2c::WriteRead for VirtualLp586x {
+    type Error = VirtualBusError;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        i2c::Write::write(self, address, bytes)?;
+        i2c::Read::read(self, address, buffer)
+    }
+}
+
+impl<DV, DM> Lp586x<DV, LoopbackInterface, DM> {
+    /// Destroys the driver and returns the loopback interface.
+    pub fn release(self) -> LoopbackInterface {
+        self.interface
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Lp5862, PwmAccess};
+
+    #[test]
+    fn test_loopback_interface() {
+        let mut ledmatrix = Lp5862::new(LoopbackInterface::new())
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+
+        ledmatrix.set_pwm(1, &[0x1234u16]).unwrap();
+        assert_eq!(ledmatrix.get_pwm(1).unwrap(), 0x1234u16);
+
+        ledmatrix.interface.inject_fault(18 + 9, FaultKind::Short);
+        assert!(ledmatrix
+            .get_global_fault_state()
+            .unwrap()
+            .led_short_detected());
+
+        let mut faults = Vec::new();
+        ledmatrix
+            .stream_fault_states(|dot, kind| faults.push((dot.index(), kind)))
+            .unwrap();
+        assert_eq!(faults, vec![(18 + 9, FaultKind::Short)]);
+
+        ledmatrix.clear_led_short_fault().unwrap();
+        assert!(!ledmatrix
+            .get_global_fault_state()
+            .unwrap()
+            .led_short_detected());
+
+        let interface = ledmatrix.release();
+        assert_eq!(interface.register(0x000), 1);
+        assert_eq!(interface.register(0x202), 0x34);
+        assert_eq!(interface.register(0x005), 0xff);
+    }
+
+    #[cfg(not(feature = "eh1_0"))]
+    #[test]
+    fn test_virtual_chip_over_i2c() {
+        use crate::{interface::I2cInterface, Lp5860};
+
+        // small transfers to exercise chunking at and across the register pages
+        let interface =
+            I2cInterface::<_, 33>::with_max_transfer_size(VirtualLp586x::new(0x40), 0x40);
+        let mut ledmatrix = Lp5860::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+
+        let frame: Vec<u16> = (0..198).map(|dot| dot * 0x0101 + 1).collect();
+        ledmatrix.set_pwm(0, &frame).unwrap();
+        ledmatrix.set_dot_current(150, &[0x7f, 0x80]).unwrap();
+        assert_eq!(ledmatrix.get_pwm(197).unwrap(), frame[197]);
+
+        let mut chip = ledmatrix.release();
+        assert!((0..198).all(|dot| chip.pwm(dot) == frame[dot as usize]));
+        assert_eq!(chip.chip().register(0x100 + 151), 0x80);
+    }
+}
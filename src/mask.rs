@@ -0,0 +1,120 @@
+//! Limiting frame writes to the dots of a single [`DotGroup`].
+
+use core::marker::PhantomData;
+
+use crate::{
+    interface::RegisterAccess, DeviceVariant, DotGroup, Error, Lp586x, PwmAccess, MAX_DOTS,
+};
+
+/// Set of dots assigned to one [`DotGroup`], e.g. all red LEDs of an RGB matrix.
+///
+/// Computed once from the group assignments passed to [`Lp586x::set_dot_groups`] and
+/// used with [`Lp586x::set_pwm_masked`] to update a single color channel of a frame.
+#[derive(Debug, Clone)]
+pub struct GroupMask<DV> {
+    dots: [bool; MAX_DOTS],
+    _phantom_data: PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant> GroupMask<DV> {
+    /// Mask of the dots assigned to `group` in `assignments`, which holds the group of
+    /// every dot starting at L0-CS0. Dots past the end of `assignments` are excluded.
+    pub fn new(assignments: &[DotGroup], group: DotGroup) -> Self {
+        let mut dots = [false; MAX_DOTS];
+        dots.iter_mut()
+            .zip(assignments.iter().take(DV::NUM_DOTS as usize))
+            .for_each(|(dot, assigned)| *dot = *assigned == group);
+
+        Self {
+            dots,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// True, if `dot` is part of the mask.
+    pub fn contains(&self, dot: u16) -> bool {
+        self.dots.get(dot as usize).copied().unwrap_or(false)
+    }
+
+    /// Consecutive runs of masked dots, as start dot and number of dots.
+    pub fn runs(&self) -> impl Iterator<Item = (u16, usize)> + '_ {
+        let num_dots = DV::NUM_DOTS as usize;
+        let mut dot = 0;
+
+        core::iter::from_fn(move || {
+            while dot < num_dots && !self.dots[dot] {
+                dot += 1;
+            }
+            let start = dot;
+            while dot < num_dots && self.dots[dot] {
+                dot += 1;
+            }
+
+            (start < dot).then(|| (start as u16, dot - start))
+        })
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Writes the PWM values of `frame`, holding a value for every dot starting at
+    /// L0-CS0, but only of the dots in `mask`, one write per run of consecutive dots.
+    pub fn set_pwm_masked<T>(&mut self, frame: &[T], mask: &GroupMask<DV>) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        for (start, len) in mask.runs() {
+            let start_index = start as usize;
+            if start_index >= frame.len() {
+                break;
+            }
+            let end = (start_index + len).min(frame.len());
+
+            self.set_pwm(start, &frame[start_index..end])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861,
+    };
+
+    #[test]
+    fn test_group_mask() {
+        let rgb = [DotGroup::Group0, DotGroup::Group1, DotGroup::Group2];
+        let assignments: Vec<DotGroup> = rgb.iter().copied().cycle().take(18).collect();
+        let mut red = GroupMask::new(&assignments, DotGroup::Group0);
+        assert!(red.contains(3));
+        assert!(!red.contains(4));
+
+        red.dots[4] = true;
+        let frame: Vec<u8> = (0..18).collect();
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![0]),
+            Access::WriteRegisters(0x200 + 3, vec![3, 4]),
+            Access::WriteRegisters(0x200 + 6, vec![6]),
+            Access::WriteRegisters(0x200 + 9, vec![9]),
+            Access::WriteRegisters(0x200 + 12, vec![12]),
+            Access::WriteRegisters(0x200 + 15, vec![15]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        ledmatrix.set_pwm_masked(&frame, &red).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
@@ -0,0 +1,174 @@
+//! Showing identical frames on two controllers, e.g. front and back of a sign.
+
+use embedded_hal::{blocking::delay::DelayUs, digital::v2::OutputPin};
+
+use crate::{
+    interface::RegisterAccess, vsync::VsyncError, DeviceVariant, Error, Lp586x, PwmAccess,
+};
+
+/// How [`MirroredDisplay`] handles a failure of one of the two drivers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MirrorPolicy {
+    /// Abort on the first failure, the secondary is not written if the primary fails
+    #[default]
+    FailFast,
+    /// Write both drivers and only fail if both failed, see [`MirroredDisplay::failed_side`]
+    ContinueOnOneFailure,
+}
+
+/// One of the two drivers of a [`MirroredDisplay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorSide {
+    Primary,
+    Secondary,
+}
+
+/// Two drivers of the same variant showing identical frames.
+pub struct MirroredDisplay<DV, I, DM> {
+    primary: Lp586x<DV, I, DM>,
+    secondary: Lp586x<DV, I, DM>,
+    policy: MirrorPolicy,
+    failed_side: Option<MirrorSide>,
+}
+
+impl<DV: DeviceVariant, I, DM, IE> MirroredDisplay<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Takes ownership of both drivers.
+    pub fn new(
+        primary: Lp586x<DV, I, DM>,
+        secondary: Lp586x<DV, I, DM>,
+        policy: MirrorPolicy,
+    ) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy,
+            failed_side: None,
+        }
+    }
+
+    /// Driver that failed during the last write, if the other one succeeded.
+    /// Only ever set with [`MirrorPolicy::ContinueOnOneFailure`].
+    pub fn failed_side(&self) -> Option<MirrorSide> {
+        self.failed_side
+    }
+
+    /// Access one of the drivers, e.g. for per side dot currents.
+    pub fn driver(&mut self, side: MirrorSide) -> &mut Lp586x<DV, I, DM> {
+        match side {
+            MirrorSide::Primary => &mut self.primary,
+            MirrorSide::Secondary => &mut self.secondary,
+        }
+    }
+
+    /// Destroys the wrapper and returns the primary and secondary driver.
+    pub fn into_inner(self) -> (Lp586x<DV, I, DM>, Lp586x<DV, I, DM>) {
+        (self.primary, self.secondary)
+    }
+
+    /// Writes PWM `values` starting at dot `start` to both drivers.
+    pub fn set_pwm<T>(&mut self, start: u16, values: &[T]) -> Result<(), Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.failed_side = None;
+
+        match self.policy {
+            MirrorPolicy::FailFast => {
+                self.primary.set_pwm(start, values)?;
+                self.secondary.set_pwm(start, values)
+            }
+            MirrorPolicy::ContinueOnOneFailure => {
+                match (
+                    self.primary.set_pwm(start, values),
+                    self.secondary.set_pwm(start, values),
+                ) {
+                    (Err(error), Err(_)) => Err(error),
+                    (Err(_), Ok(())) => {
+                        self.failed_side = Some(MirrorSide::Primary);
+                        Ok(())
+                    }
+                    (Ok(()), Err(_)) => {
+                        self.failed_side = Some(MirrorSide::Secondary);
+                        Ok(())
+                    }
+                    (Ok(()), Ok(())) => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Writes PWM `values` to both drivers and latches them with a single pulse on
+    /// `vsync`, which has to be connected to the VSYNC input of both controllers
+    /// (Mode 2 and Mode 3). Both sides thus switch frames at the same time.
+    pub fn flush<T, P: OutputPin, D: DelayUs<u32>>(
+        &mut self,
+        start: u16,
+        values: &[T],
+        vsync: &mut P,
+        delay: &mut D,
+    ) -> Result<(), VsyncError<IE, P::Error>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.set_pwm(start, values).map_err(VsyncError::Driver)?;
+
+        crate::vsync::pulse_pin(vsync, Some(delay)).map_err(VsyncError::Pin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        DataMode8Bit, Lp5861, Variant1,
+    };
+
+    fn driver(accesses: Vec<Access>) -> Lp586x<Variant1, MockInterface, DataMode8Bit> {
+        let mut expected = vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+        ];
+        expected.extend(accesses);
+
+        Lp5861::new(MockInterface::new(expected))
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mirrored_display() {
+        let mut display = MirroredDisplay::new(
+            driver(vec![Access::FailWrite(0x200)]),
+            driver(vec![]),
+            MirrorPolicy::FailFast,
+        );
+        assert!(display.set_pwm(0, &[1u8, 2]).is_err());
+        let (primary, secondary) = display.into_inner();
+        primary.release().done();
+        secondary.release().done();
+
+        let mut display = MirroredDisplay::new(
+            driver(vec![
+                Access::FailWrite(0x200),
+                Access::WriteRegisters(0x200, vec![1, 2]),
+            ]),
+            driver(vec![
+                Access::WriteRegisters(0x200, vec![1, 2]),
+                Access::WriteRegisters(0x200, vec![1, 2]),
+            ]),
+            MirrorPolicy::ContinueOnOneFailure,
+        );
+        display.set_pwm(0, &[1u8, 2]).unwrap();
+        assert_eq!(display.failed_side(), Some(MirrorSide::Primary));
+        display.set_pwm(0, &[1u8, 2]).unwrap();
+        assert_eq!(display.failed_side(), None);
+        let (primary, secondary) = display.into_inner();
+        primary.release().done();
+        secondary.release().done();
+    }
+}
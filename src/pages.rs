@@ -0,0 +1,158 @@
+//! Multiple frames of PWM values kept in MCU RAM, for instant page flips.
+
+use core::marker::PhantomData;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{interface::RegisterAccess, DeviceVariant, Error, Lp586x, PwmAccess, MAX_DOTS};
+
+/// `N` frames of PWM values of type `T` (`u8` or `u16`, matching the data mode).
+///
+/// Render content into a page once with [`Pages::page_mut`] and show it any time with
+/// [`Pages::show_page`], which writes the whole frame in a single burst. With
+/// [`crate::DataRefMode::Mode2`] or [`crate::DataRefMode::Mode3`] the new frame takes
+/// effect on the next VSYNC pulse, so the flip is never visible halfway.
+pub struct Pages<DV, T, const N: usize> {
+    frames: [[T; MAX_DOTS]; N],
+    _phantom_data: PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant, T: Copy + Default, const N: usize> Pages<DV, T, N> {
+    /// Creates `N` pages with all dots off.
+    pub fn new() -> Self {
+        Self {
+            frames: [[T::default(); MAX_DOTS]; N],
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// PWM values of page `page`, indexed by dot.
+    pub fn page(&self, page: usize) -> &[T] {
+        &self.frames[page][..DV::NUM_DOTS as usize]
+    }
+
+    /// Mutable PWM values of page `page`, indexed by dot.
+    pub fn page_mut(&mut self, page: usize) -> &mut [T] {
+        &mut self.frames[page][..DV::NUM_DOTS as usize]
+    }
+
+    /// Writes page `page` to `driver`.
+    pub fn show_page<I, DM, IE>(
+        &self,
+        driver: &mut Lp586x<DV, I, DM>,
+        page: usize,
+    ) -> Result<(), Error<IE>>
+    where
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        driver.set_pwm(0, self.page(page))
+    }
+
+    /// Fades from page `from` to page `to` in `steps` frames, waiting `step_delay_us`
+    /// between frames. Every dot is interpolated linearly, the last frame equals `to`.
+    pub fn crossfade<I, DM, IE, D>(
+        &self,
+        driver: &mut Lp586x<DV, I, DM>,
+        from: usize,
+        to: usize,
+        steps: u16,
+        step_delay_us: u32,
+        delay: &mut D,
+    ) -> Result<(), Error<IE>>
+    where
+        T: Into<u32> + TryFrom<u32>,
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+        D: DelayUs<u32>,
+    {
+        let steps = steps.max(1) as i64;
+        let mut frame = [T::default(); MAX_DOTS];
+
+        for step in 1..=steps {
+            frame
+                .iter_mut()
+                .zip(self.page(from).iter().zip(self.page(to)))
+                .for_each(|(value, (&from, &to))| {
+                    let from: i64 = from.into().into();
+                    let to: i64 = to.into().into();
+                    let interpolated = from + (to - from) * step / steps;
+                    *value = T::try_from(interpolated as u32).unwrap_or_default();
+                });
+
+            driver.set_pwm(0, &frame[..DV::NUM_DOTS as usize])?;
+
+            if step < steps {
+                delay.delay_us(step_delay_us);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DV: DeviceVariant, T: Copy + Default, const N: usize> Default for Pages<DV, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface, NoDelay},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_show_page() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![0; 18]),
+            Access::WriteRegisters(0x200, (0..18).collect()),
+        ]);
+
+        let mut pages = Pages::<Variant1, u8, 2>::new();
+        pages
+            .page_mut(1)
+            .iter_mut()
+            .enumerate()
+            .for_each(|(dot, value)| *value = dot as u8);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        pages.show_page(&mut ledmatrix, 0).unwrap();
+        pages.show_page(&mut ledmatrix, 1).unwrap();
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_crossfade() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, [0xff, 0x55].repeat(18)),
+            Access::WriteRegisters(0x200, [0xff, 0xaa].repeat(18)),
+            Access::WriteRegisters(0x200, [0xff; 36].to_vec()),
+        ]);
+
+        let mut pages = Pages::<Variant1, u16, 2>::new();
+        pages.page_mut(0).fill(0x100);
+        pages.page_mut(1).fill(0xffff);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_16bit_data_mode()
+            .unwrap();
+        pages
+            .crossfade(&mut ledmatrix, 0, 1, 3, 1000, &mut NoDelay)
+            .unwrap();
+
+        ledmatrix.release().done();
+    }
+}
@@ -0,0 +1,123 @@
+//! Current settings derived from LED datasheet ratings, for hardware bring-up.
+//!
+//! The output current of a dot is `I_MAX * CC / 127 * DC / 255`, with `I_MAX` the
+//! [`CurrentSetting`], `CC` the 7 bit current of the dot's color group and `DC` the
+//! dot current. [`Profile::for_leds`] does this math once, from the rated forward
+//! current of the LEDs of each color group.
+
+use crate::{
+    configuration::Configuration, interface::RegisterAccess, CurrentSetting, DataModeMarker,
+    DeviceVariant, Error, Lp586x, MAX_DOTS,
+};
+
+/// All maximum current settings, in ascending order
+const CURRENT_SETTINGS: [CurrentSetting; 8] = [
+    CurrentSetting::Max3mA,
+    CurrentSetting::Max5mA,
+    CurrentSetting::Max10mA,
+    CurrentSetting::Max15mA,
+    CurrentSetting::Max20mA,
+    CurrentSetting::Max30mA,
+    CurrentSetting::Max40mA,
+    CurrentSetting::Max50mA,
+];
+
+/// Maximum current, group currents and dot current keeping every LED within its
+/// derated current rating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    /// Maximum current setting, see [`Profile::configuration`]
+    pub maximum_current: CurrentSetting,
+    /// Current scaling (0..127) of the color groups, group 0 to group 2
+    pub group_currents: [u8; 3],
+    /// Current of every dot
+    pub dot_current: u8,
+}
+
+impl Profile {
+    /// Profile for LEDs rated for `rated_current_ua` µA (group 0 to group 2, e.g. red,
+    /// green and blue), derated to `derating_percent` percent of the rating.
+    ///
+    /// The smallest maximum current setting covering all derated currents is used, for
+    /// the finest group current resolution. Currents are rounded down, so no LED
+    /// exceeds its derated rating, which is treated as a peak current limit. Ratings
+    /// beyond the 50 mA setting are capped to it.
+    pub fn for_leds(rated_current_ua: [u32; 3], derating_percent: u8) -> Self {
+        let allowed_ua =
+            rated_current_ua.map(|rating| rating / 100 * derating_percent.min(100) as u32);
+        let highest_ua = allowed_ua.into_iter().max().unwrap_or(0);
+
+        let maximum_current = CURRENT_SETTINGS
+            .into_iter()
+            .find(|setting| setting.max_current_ua() >= highest_ua)
+            .unwrap_or(CurrentSetting::Max50mA);
+        let max_ua = maximum_current.max_current_ua();
+
+        Self {
+            maximum_current,
+            group_currents: allowed_ua.map(|allowed| (allowed.min(max_ua) * 127 / max_ua) as u8),
+            dot_current: 0xff,
+        }
+    }
+
+    /// Current of the dots of `group` (0 to 2) in µA, as set by this profile.
+    pub fn group_current_ua(&self, group: usize) -> u32 {
+        self.maximum_current.max_current_ua() * self.group_currents[group] as u32 / 127
+            * self.dot_current as u32
+            / 255
+    }
+
+    /// `configuration` with the maximum current of this profile.
+    pub fn configuration(&self, configuration: Configuration) -> Configuration {
+        configuration.with_maximum_current(self.maximum_current)
+    }
+
+    /// Writes the group currents and the dot currents of all dots to `driver`. The
+    /// maximum current is part of the configuration, see [`Profile::configuration`].
+    pub fn apply<DV, I, DM, IE>(&self, driver: &mut Lp586x<DV, I, DM>) -> Result<(), Error<IE>>
+    where
+        DV: DeviceVariant,
+        I: RegisterAccess<Error = Error<IE>>,
+        DM: DataModeMarker,
+    {
+        driver.set_all_group_currents(self.group_currents)?;
+        driver.set_dot_current(0, &[self.dot_current; MAX_DOTS][..DV::NUM_DOTS as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_profile_for_leds() {
+        let profile = Profile::for_leds([20_000, 15_000, 10_000], 80);
+        assert_eq!(profile.maximum_current, CurrentSetting::Max20mA);
+        assert_eq!(profile.group_currents, [101, 76, 50]);
+        assert!(profile.group_current_ua(0) <= 16_000);
+        assert!(profile.group_current_ua(2) <= 8_000);
+
+        let capped = Profile::for_leds([100_000, 60_000, 0], 100);
+        assert_eq!(capped.maximum_current, CurrentSetting::Max50mA);
+        assert_eq!(capped.group_currents, [127, 127, 0]);
+
+        let configuration = profile.configuration(Configuration::new::<Variant1>());
+        assert_eq!(configuration.maximum_current, CurrentSetting::Max20mA);
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x009, vec![101, 76, 50]),
+            Access::WriteRegisters(0x100, vec![0xff; 18]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        profile.apply(&mut ledmatrix).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
@@ -62,6 +62,37 @@ impl Register {
     }
 }
 
+/// Symbolic name of the register at `address`, if any. Registers belonging to the
+/// per-dot register ranges are named after their range.
+#[cfg(feature = "debug-names")]
+pub const fn register_name(address: u16) -> Option<&'static str> {
+    Some(match address {
+        Register::CHIP_EN => "CHIP_EN",
+        Register::DEV_INITIAL => "DEV_INITIAL",
+        Register::DEV_CONFIG1 => "DEV_CONFIG1",
+        Register::DEV_CONFIG2 => "DEV_CONFIG2",
+        Register::DEV_CONFIG3 => "DEV_CONFIG3",
+        Register::GLOBAL_BRIGHTNESS => "GLOBAL_BRIGHTNESS",
+        Register::GROUP0_BRIGHTNESS => "GROUP0_BRIGHTNESS",
+        Register::GROUP1_BRIGHTNESS => "GROUP1_BRIGHTNESS",
+        Register::GROUP2_BRIGHTNESS => "GROUP2_BRIGHTNESS",
+        Register::GROUP0_CURRENT => "GROUP0_CURRENT",
+        Register::GROUP1_CURRENT => "GROUP1_CURRENT",
+        Register::GROUP2_CURRENT => "GROUP2_CURRENT",
+        0x00c..=0x042 => "DOT_GROUP_SELECT",
+        0x043..=0x063 => "DOT_ONOFF",
+        Register::FAULT_STATE => "FAULT_STATE",
+        0x065..=0x085 => "DOT_LOD",
+        0x086..=0x0a6 => "DOT_LSD",
+        Register::LOD_CLEAR => "LOD_CLEAR",
+        Register::LSD_CLEAR => "LSD_CLEAR",
+        Register::RESET => "RESET",
+        0x100..=0x1c5 => "DOT_CURRENT",
+        0x200..=0x38b => "PWM_BRIGHTNESS",
+        _ => return None,
+    })
+}
+
 /// Bitflags for registers
 pub struct BitFlags;
 #[allow(dead_code)]
@@ -99,3 +130,147 @@ impl BitFlags {
     pub const FAULT_STATE_GLOBAL_LSD: u8 = (1 << 0);
     pub const FAULT_STATE_GLOBAL_LOD: u8 = (1 << 1);
 }
+
+#[cfg(all(test, feature = "debug-names"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_name() {
+        assert_eq!(register_name(Register::CHIP_EN), Some("CHIP_EN"));
+        assert_eq!(register_name(Register::dot_onoff(32)), Some("DOT_ONOFF"));
+        assert_eq!(register_name(Register::FAULT_STATE), Some("FAULT_STATE"));
+        assert_eq!(
+            register_name(Register::pwm_brightness(395)),
+            Some("PWM_BRIGHTNESS")
+        );
+        assert_eq!(register_name(0x0aa), None);
+        assert_eq!(register_name(0x38c), None);
+    }
+}
+
+/// Register map of SNVU786 as tables, checked against the register constants and the
+/// encoding and decoding of [`crate::configuration::Configuration`].
+#[cfg(test)]
+mod register_map_tests {
+    use super::*;
+    use crate::{
+        configuration::Configuration, CurrentSetting, DataRefMode, DownDeghost, LineBlankingTime,
+        LowBrightnessCompensation, PwmFrequency, PwmScaleMode, UpDeghost, Variant0,
+    };
+
+    /// Addresses of the single registers and first registers of the per dot ranges
+    const ADDRESSES: [(u16, u16); 18] = [
+        (Register::CHIP_EN, 0x000),
+        (Register::DEV_INITIAL, 0x001),
+        (Register::DEV_CONFIG1, 0x002),
+        (Register::DEV_CONFIG2, 0x003),
+        (Register::DEV_CONFIG3, 0x004),
+        (Register::GLOBAL_BRIGHTNESS, 0x005),
+        (Register::GROUP0_BRIGHTNESS, 0x006),
+        (Register::GROUP2_BRIGHTNESS, 0x008),
+        (Register::GROUP0_CURRENT, 0x009),
+        (Register::GROUP2_CURRENT, 0x00b),
+        (Register::DOT_GROUP_SELECT_START, 0x00c),
+        (Register::DOT_ONOFF_START, 0x043),
+        (Register::FAULT_STATE, 0x064),
+        (Register::DOT_LOD_START, 0x065),
+        (Register::DOT_LSD_START, 0x086),
+        (Register::RESET, 0x0a9),
+        (Register::DOT_CURRENT_START, 0x100),
+        (Register::PWM_BRIGHTNESS_START, 0x200),
+    ];
+
+    /// Reset values of `DEV_INITIAL` to `DEV_CONFIG3`
+    const CONFIG_RESET_VALUES: [u8; 4] = [0x5e, 0x00, 0x00, 0x57];
+
+    type Field = (fn(Configuration) -> Configuration, usize, u8);
+
+    /// Configuration fields, as a change from the default configuration and the bits
+    /// of the field in `DEV_INITIAL` to `DEV_CONFIG3` (register index, mask)
+    const FIELDS: [Field; 17] = [
+        (
+            |c| c.with_pwm_frequency(PwmFrequency::Pwm125kHz),
+            0,
+            0b0000_0001,
+        ),
+        (|c| c.with_data_ref_mode(DataRefMode::Mode2), 0, 0b0000_0110),
+        (|c| c.with_max_line_num(5), 0, 0b0111_1000),
+        (|c| c.with_cs_turn_on_delay(true), 1, 0b0000_0001),
+        (|c| c.with_pwm_phase_shift(true), 1, 0b0000_0010),
+        (
+            |c| c.with_pwm_scale_mode(PwmScaleMode::Exponential),
+            1,
+            0b0000_0100,
+        ),
+        (
+            |c| c.with_switch_blanking_time(LineBlankingTime::Blank0_5us),
+            1,
+            0b0000_1000,
+        ),
+        (|c| c.with_lsd_removal(true), 2, 0b0000_0001),
+        (|c| c.with_lod_removal(true), 2, 0b0000_0010),
+        (
+            |c| c.with_comp_group1(LowBrightnessCompensation::Mode3),
+            2,
+            0b0000_1100,
+        ),
+        (
+            |c| c.with_comp_group2(LowBrightnessCompensation::Mode2),
+            2,
+            0b0011_0000,
+        ),
+        (
+            |c| c.with_comp_group3(LowBrightnessCompensation::Mode1),
+            2,
+            0b1100_0000,
+        ),
+        (|c| c.with_up_deghost_enable(false), 3, 0b0000_0001),
+        (
+            |c| c.with_maximum_current(CurrentSetting::Max50mA),
+            3,
+            0b0000_1110,
+        ),
+        (|c| c.with_up_deghost(UpDeghost::Gnd), 3, 0b0011_0000),
+        (|c| c.with_down_deghost(DownDeghost::Strong), 3, 0b1100_0000),
+        (|c| c.with_down_deghost(DownDeghost::None), 3, 0b1100_0000),
+    ];
+
+    #[test]
+    fn test_register_addresses() {
+        for (register, address) in ADDRESSES {
+            assert_eq!(register, address);
+        }
+        assert_eq!(Register::dot_lod(32), 0x085);
+        assert_eq!(Register::dot_current(197), 0x1c5);
+    }
+
+    #[test]
+    fn test_config_reset_values() {
+        let reset = Configuration::from_register_values(CONFIG_RESET_VALUES);
+        assert_eq!(reset, Configuration::new::<Variant0>());
+        assert_eq!(reset.max_line_num, 11);
+    }
+
+    #[test]
+    fn test_config_fields() {
+        let default = Configuration::new::<Variant0>();
+        let default_values = default.register_values();
+
+        for (change, register, mask) in FIELDS {
+            let configuration = change(default);
+            let values = configuration.register_values();
+
+            for (index, (value, default_value)) in values.iter().zip(default_values).enumerate() {
+                let changed = value ^ default_value;
+                if index == register {
+                    assert!(changed != 0 && changed & !mask == 0, "{configuration:?}");
+                } else {
+                    assert_eq!(changed, 0, "{configuration:?}");
+                }
+            }
+
+            assert_eq!(Configuration::from_register_values(values), configuration);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Production self test lighting all dots and reporting open and shorted LEDs.
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::RegisterAccess, register::Register, DataModeMarker, DeviceVariant, Error,
+    GlobalFaultState, Lp586x, Op, MAX_DOTS,
+};
+
+/// Result of [`Lp586x::run_self_test`].
+#[derive(Debug)]
+pub struct SelfTestReport {
+    num_dots: u16,
+    open: [bool; MAX_DOTS],
+    short: [bool; MAX_DOTS],
+    global_fault_state: GlobalFaultState,
+}
+
+impl SelfTestReport {
+    /// True, if no dot was detected open or shorted.
+    pub fn passed(&self) -> bool {
+        self.open_dots().next().is_none() && self.short_dots().next().is_none()
+    }
+
+    /// Indices of all dots detected open (dead or not populated).
+    pub fn open_dots(&self) -> impl Iterator<Item = u16> + '_ {
+        Self::faulty(&self.open[..self.num_dots as usize])
+    }
+
+    /// Indices of all dots detected shorted.
+    pub fn short_dots(&self) -> impl Iterator<Item = u16> + '_ {
+        Self::faulty(&self.short[..self.num_dots as usize])
+    }
+
+    /// Global fault state sampled during the test.
+    pub fn global_fault_state(&self) -> &GlobalFaultState {
+        &self.global_fault_state
+    }
+
+    fn faulty(states: &[bool]) -> impl Iterator<Item = u16> + '_ {
+        states
+            .iter()
+            .enumerate()
+            .filter(|(_, faulty)| **faulty)
+            .map(|(dot, _)| dot as u16)
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Dot current used during the self test (about a quarter of the maximum current).
+    pub const SELF_TEST_DOT_CURRENT: u8 = 0x40;
+
+    /// Time the self test keeps all dots on before sampling the fault registers.
+    pub const T_SELF_TEST_SETTLE_US: u32 = 10_000;
+
+    /// Runs a self test, reporting open and shorted dots.
+    ///
+    /// All dots are turned on at full PWM with [`Self::SELF_TEST_DOT_CURRENT`], the LED
+    /// open and short detection is sampled and all dots are turned off again. This
+    /// overwrites the global brightness, dot current and PWM registers. In Mode 2 and 3
+    /// VSYNC has to be running for the PWM values to take effect.
+    pub fn run_self_test<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SelfTestReport, Error<IE>> {
+        // Covers the PWM registers of the 8 bit as well as the 16 bit data mode
+        let pwm_registers = DV::NUM_DOTS as usize * 2;
+
+        self.clear_led_open_fault()?;
+        self.clear_led_short_fault()?;
+        self.set_global_brightness(0xff)?;
+        self.set_dot_current(
+            0,
+            &[Self::SELF_TEST_DOT_CURRENT; MAX_DOTS][..DV::NUM_DOTS as usize],
+        )?;
+        self.interface
+            .write_registers(
+                Register::PWM_BRIGHTNESS_START,
+                &[0xff; MAX_DOTS * 2][..pwm_registers],
+            )
+            .map_err(|e| e.tagged(Op::WriteFrame))?;
+
+        delay.delay_us(Self::T_SELF_TEST_SETTLE_US);
+
+        let mut report = SelfTestReport {
+            num_dots: DV::NUM_DOTS,
+            open: [false; MAX_DOTS],
+            short: [false; MAX_DOTS],
+            global_fault_state: self.get_global_fault_state()?,
+        };
+        self.get_led_open_states(&mut report.open)?;
+        self.get_led_short_states(&mut report.short)?;
+
+        self.interface
+            .write_registers(
+                Register::PWM_BRIGHTNESS_START,
+                &[0x00; MAX_DOTS * 2][..pwm_registers],
+            )
+            .map_err(|e| e.tagged(Op::WriteFrame))?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        interface::mock::{Access, MockInterface, NoDelay},
+        Lp5861,
+    };
+
+    #[test]
+    fn test_run_self_test() {
+        let mut lod = vec![0u8; 33];
+        lod[0] = 0b0000_0100;
+        let mut lsd = vec![0u8; 33];
+        lsd[2] = 0b0000_0010;
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x0a7, 0xf),
+            Access::WriteRegister(0x0a8, 0xf),
+            Access::WriteRegister(0x005, 0xff),
+            Access::WriteRegisters(0x100, vec![0x40; 18]),
+            Access::WriteRegisters(0x200, vec![0xff; 36]),
+            Access::ReadRegister(0x064, 0b11),
+            Access::ReadRegisters(0x065, lod),
+            Access::ReadRegisters(0x086, lsd),
+            Access::WriteRegisters(0x200, vec![0x00; 36]),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+        let report = ledmatrix.run_self_test(&mut NoDelay).unwrap();
+
+        assert!(!report.passed());
+        assert!(report.global_fault_state().led_open_detected());
+        assert_eq!(report.open_dots().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(report.short_dots().collect::<Vec<_>>(), vec![17]);
+
+        ledmatrix.release().done();
+    }
+}
@@ -0,0 +1,147 @@
+//! Sharing one driver between a rendering and a diagnostics task.
+//!
+//! [`SharedLp586x::split`] hands out a [`Renderer`] and a [`Diagnostics`] handle, which
+//! can be moved to different tasks (e.g. RTIC or Embassy). Every operation runs in a
+//! critical section, so operations of the two handles never interleave on the bus.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::{
+    fault_poller::{FaultChange, FaultPoller},
+    interface::RegisterAccess,
+    DataModeMarker, DeviceVariant, Error, GlobalFaultState, Lp586x, PwmAccess,
+};
+
+/// Driver shared between a [`Renderer`] and a [`Diagnostics`] handle.
+pub struct SharedLp586x<DV, I, DM> {
+    driver: Mutex<RefCell<Lp586x<DV, I, DM>>>,
+}
+
+/// Handle for writing PWM values of a [`SharedLp586x`].
+pub struct Renderer<'a, DV, I, DM> {
+    shared: &'a SharedLp586x<DV, I, DM>,
+}
+
+/// Handle for reading and clearing the fault states of a [`SharedLp586x`].
+pub struct Diagnostics<'a, DV, I, DM> {
+    shared: &'a SharedLp586x<DV, I, DM>,
+}
+
+impl<DV, I, DM> SharedLp586x<DV, I, DM> {
+    /// Takes ownership of `driver`.
+    pub const fn new(driver: Lp586x<DV, I, DM>) -> Self {
+        Self {
+            driver: Mutex::new(RefCell::new(driver)),
+        }
+    }
+
+    /// Splits into a rendering and a diagnostics handle.
+    pub fn split(&self) -> (Renderer<'_, DV, I, DM>, Diagnostics<'_, DV, I, DM>) {
+        (Renderer { shared: self }, Diagnostics { shared: self })
+    }
+
+    /// Runs `f` with exclusive access to the driver, e.g. for configuration.
+    pub fn with_driver<R>(&self, f: impl FnOnce(&mut Lp586x<DV, I, DM>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.driver.borrow_ref_mut(cs)))
+    }
+
+    /// Destroys the shared driver and returns the driver.
+    pub fn into_inner(self) -> Lp586x<DV, I, DM> {
+        self.driver.into_inner().into_inner()
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Renderer<'_, DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Writes PWM `values` starting at dot `start`, see [`PwmAccess::set_pwm`].
+    pub fn set_pwm<T>(&self, start: u16, values: &[T]) -> Result<(), Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.shared
+            .with_driver(|driver| driver.set_pwm(start, values))
+    }
+
+    /// Reads the PWM value of `dot`, see [`PwmAccess::get_pwm`].
+    pub fn get_pwm<T>(&self, dot: u16) -> Result<T, Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.shared.with_driver(|driver| driver.get_pwm(dot))
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Diagnostics<'_, DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// See [`Lp586x::get_global_fault_state`].
+    pub fn get_global_fault_state(&self) -> Result<GlobalFaultState, Error<IE>> {
+        self.shared
+            .with_driver(|driver| driver.get_global_fault_state())
+    }
+
+    /// See [`Lp586x::clear_led_open_fault`].
+    pub fn clear_led_open_fault(&self) -> Result<(), Error<IE>> {
+        self.shared
+            .with_driver(|driver| driver.clear_led_open_fault())
+    }
+
+    /// See [`Lp586x::clear_led_short_fault`].
+    pub fn clear_led_short_fault(&self) -> Result<(), Error<IE>> {
+        self.shared
+            .with_driver(|driver| driver.clear_led_short_fault())
+    }
+
+    /// Ticks `poller`, see [`FaultPoller::tick`]. A tick reads at most one line, which
+    /// keeps the critical section short. `on_change` is called inside of it.
+    pub fn tick(
+        &self,
+        poller: &mut FaultPoller<DV>,
+        on_change: impl FnMut(FaultChange<DV>),
+    ) -> Result<(), Error<IE>> {
+        self.shared
+            .with_driver(|driver| poller.tick(driver, on_change))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861,
+    };
+
+    #[test]
+    fn test_split() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![1, 2]),
+            Access::ReadRegister(0x064, 0),
+            Access::ReadRegister(0x201, 2),
+        ]);
+
+        let ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let shared = SharedLp586x::new(ledmatrix);
+        let (renderer, diagnostics) = shared.split();
+
+        renderer.set_pwm(0, &[1u8, 2]).unwrap();
+        assert!(!diagnostics
+            .get_global_fault_state()
+            .unwrap()
+            .led_open_detected());
+        assert_eq!(renderer.get_pwm::<u8>(1).unwrap(), 2);
+
+        shared.into_inner().release().done();
+    }
+}
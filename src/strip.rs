@@ -0,0 +1,116 @@
+//! Addressing the matrix as a single one dimensional LED strip.
+
+use crate::{interface::RegisterAccess, DeviceVariant, Error, Lp586x, PwmAccess, MAX_DOTS};
+
+/// Presents the matrix as a strip of LEDs, line after line.
+///
+/// With serpentine ordering every odd line runs backwards (from the last to the first
+/// current sink), matching panels wired in a zigzag.
+pub struct StripView<'a, DV, I, DM> {
+    driver: &'a mut Lp586x<DV, I, DM>,
+    serpentine: bool,
+}
+
+impl<'a, DV: DeviceVariant, I, DM, IE> StripView<'a, DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+{
+    /// Create a strip view of `driver`.
+    pub fn new(driver: &'a mut Lp586x<DV, I, DM>, serpentine: bool) -> Self {
+        Self { driver, serpentine }
+    }
+
+    /// Number of LEDs in the strip.
+    pub fn len(&self) -> usize {
+        DV::NUM_DOTS as usize
+    }
+
+    /// Always false, every device variant has dots.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Dot index of LED `led`, or `None` if `led` is past the end of the strip.
+    pub fn dot_index(&self, led: usize) -> Option<u16> {
+        if led >= self.len() {
+            return None;
+        }
+
+        let sinks = DV::NUM_CURRENT_SINKS as usize;
+        let (line, position) = (led / sinks, led % sinks);
+        let cs = if self.serpentine && line % 2 == 1 {
+            sinks - 1 - position
+        } else {
+            position
+        };
+
+        Some((line * sinks + cs) as u16)
+    }
+
+    /// Sets the PWM value of LED `led`.
+    pub fn set_led<T>(&mut self, led: usize, value: T) -> Result<(), Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        let dot = self.dot_index(led).ok_or(Error::DotOutOfRange)?;
+        self.driver.set_pwm(dot, &[value])
+    }
+
+    /// Sets the PWM values of the first `values.len()` LEDs in a single burst.
+    pub fn set_strip<T: Copy + Default>(&mut self, values: &[T]) -> Result<(), Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        if values.len() > self.len() {
+            return Err(Error::DotOutOfRange);
+        }
+
+        let mut frame = [T::default(); MAX_DOTS];
+        let mut end = 0;
+        for (led, value) in values.iter().enumerate() {
+            let dot = self.dot_index(led).ok_or(Error::DotOutOfRange)? as usize;
+            frame[dot] = *value;
+            end = end.max(dot + 1);
+        }
+
+        self.driver.set_pwm(0, &frame[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5862,
+    };
+
+    #[test]
+    fn test_serpentine_strip() {
+        let mut frame: Vec<u8> = (0..18).collect();
+        frame.extend((18..36).rev());
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200 + 35, vec![0x42]),
+            Access::WriteRegisters(0x200, frame),
+        ]);
+
+        let mut ledmatrix = Lp5862::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        let mut strip = StripView::new(&mut ledmatrix, true);
+        assert_eq!(strip.dot_index(17), Some(17));
+        assert_eq!(strip.dot_index(18), Some(35));
+        assert_eq!(strip.dot_index(36), None);
+
+        strip.set_led(18, 0x42u8).unwrap();
+        let values: Vec<u8> = (0..36).collect();
+        strip.set_strip(&values).unwrap();
+
+        ledmatrix.release().done();
+    }
+}
@@ -0,0 +1,301 @@
+//! Graceful degradation under repeated bus errors.
+
+use crate::{
+    configuration::Configuration, interface::RegisterAccess, DataModeMarker, DeviceVariant, Error,
+    Lp586x, PwmAccess, PwmDataMode,
+};
+
+/// Health state of a [`Supervised`] driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusState {
+    /// Writes are passed on to the chip.
+    Healthy,
+    /// Too many consecutive interface errors occurred; writes are skipped and the chip
+    /// is periodically verified and re-initialized.
+    Degraded,
+}
+
+/// When to enter and how to leave the [`BusState::Degraded`] state.
+#[derive(Debug, Clone, Copy)]
+pub struct DegradationPolicy {
+    /// Number of consecutive interface errors after which the driver is degraded.
+    pub error_threshold: u8,
+    /// Number of skipped writes between recovery attempts while degraded.
+    pub recovery_interval: u16,
+}
+
+impl Default for DegradationPolicy {
+    fn default() -> Self {
+        Self {
+            error_threshold: 3,
+            recovery_interval: 50,
+        }
+    }
+}
+
+/// Driver wrapper reporting a [`BusState`] instead of returning errors on every call.
+///
+/// After [`DegradationPolicy::error_threshold`] consecutive interface errors, frame
+/// writes are skipped. Every [`DegradationPolicy::recovery_interval`] skipped writes
+/// [`Lp586x::verify_and_recover`] is attempted, and on success writes resume.
+pub struct Supervised<DV, I, DM> {
+    driver: Lp586x<DV, I, DM>,
+    configuration: Configuration,
+    policy: DegradationPolicy,
+    state: BusState,
+    consecutive_errors: u8,
+    skipped_writes: u16,
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Supervised<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: DataModeMarker,
+{
+    /// Supervise `driver`, which is configured with `configuration`.
+    pub fn new(
+        driver: Lp586x<DV, I, DM>,
+        configuration: Configuration,
+        policy: DegradationPolicy,
+    ) -> Self {
+        Self {
+            driver,
+            configuration,
+            policy,
+            state: BusState::Healthy,
+            consecutive_errors: 0,
+            skipped_writes: 0,
+        }
+    }
+
+    /// Current bus state.
+    pub fn state(&self) -> BusState {
+        self.state
+    }
+
+    /// Access the supervised driver directly, bypassing the degradation policy.
+    pub fn driver(&mut self) -> &mut Lp586x<DV, I, DM> {
+        &mut self.driver
+    }
+
+    /// Destroys the wrapper and returns the supervised driver.
+    pub fn into_inner(self) -> Lp586x<DV, I, DM> {
+        self.driver
+    }
+
+    /// Set PWM values of `values.len()` dots, starting from dot `start`, unless the
+    /// driver is degraded. Returns the resulting bus state.
+    ///
+    /// Only interface errors count towards degradation. Other errors, e.g.
+    /// [`Error::DotOutOfRange`] for an invalid `start`, are caller bugs and returned
+    /// without affecting the bus state.
+    pub fn set_pwm<T>(&mut self, start: u16, values: &[T]) -> Result<BusState, Error<IE>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        if self.state == BusState::Degraded {
+            self.skipped_writes = self.skipped_writes.saturating_add(1);
+            if self.skipped_writes < self.policy.recovery_interval {
+                return Ok(self.state);
+            }
+
+            self.skipped_writes = 0;
+            if self.driver.verify_and_recover(&self.configuration).is_err() {
+                return Ok(self.state);
+            }
+
+            self.state = BusState::Healthy;
+            self.consecutive_errors = 0;
+        }
+
+        match self.driver.set_pwm(start, values) {
+            Ok(()) => self.consecutive_errors = 0,
+            Err(Error::Interface { .. }) => {
+                self.consecutive_errors = self.consecutive_errors.saturating_add(1);
+                if self.consecutive_errors >= self.policy.error_threshold {
+                    self.state = BusState::Degraded;
+                }
+            }
+            Err(error) => return Err(error),
+        }
+
+        Ok(self.state)
+    }
+}
+
+impl<DV: DeviceVariant, I, DM, IE> Lp586x<DV, I, DM>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    DM: PwmDataMode,
+{
+    /// Writes PWM `values` starting at dot `start` in chunks of at most
+    /// [`RegisterAccess::max_contiguous_write`] registers. A chunk failing with an
+    /// interface error is retried up to `max_retries` times before giving up, earlier
+    /// chunks are not written again. Dots out of range are handled like
+    /// [`PwmAccess::set_pwm`] does, before anything is written.
+    pub fn write_frame_resilient<T>(
+        &mut self,
+        start: u16,
+        values: &[T],
+        max_retries: u8,
+    ) -> Result<(), Error<IE>>
+    where
+        Self: PwmAccess<T, Error = Error<IE>>,
+    {
+        let len = self.dots_to_write(start, values.len())?;
+        let mut dot = start;
+        let mut remaining = &values[..len];
+
+        while !remaining.is_empty() {
+            let max_registers = self
+                .interface
+                .max_contiguous_write(Self::pwm_register_for(dot));
+            let chunk_len =
+                (max_registers / DM::REGISTERS_PER_DOT as usize).clamp(1, remaining.len());
+            let (chunk, rest) = remaining.split_at(chunk_len);
+
+            let mut attempt = 0;
+            while let Err(error) = self.set_pwm(dot, chunk) {
+                if attempt == max_retries || !matches!(error, Error::Interface { .. }) {
+                    return Err(error);
+                }
+                attempt += 1;
+            }
+
+            dot += chunk_len as u16;
+            remaining = rest;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_verify_and_recover() {
+        let configuration = Configuration::new::<Variant1>();
+        let mut registers = vec![1];
+        registers.extend(configuration.register_values());
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegisters(0x000, registers),
+            Access::ReadRegisters(0x000, vec![0; 5]),
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface).unwrap();
+
+        assert!(!ledmatrix.verify_and_recover(&configuration).unwrap());
+        assert!(ledmatrix.verify_and_recover(&configuration).unwrap());
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_write_frame_resilient() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::FailWrite(0x200),
+            Access::WriteRegisters(0x200, vec![1, 2]),
+            Access::FailWrite(0x200),
+            Access::FailWrite(0x200),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        ledmatrix.write_frame_resilient(0, &[1u8, 2], 1).unwrap();
+        assert!(ledmatrix.write_frame_resilient(0, &[1u8, 2], 1).is_err());
+        assert!(matches!(
+            ledmatrix.write_frame_resilient(u16::MAX, &[1u8], 3),
+            Err(Error::DotOutOfRange)
+        ));
+        assert!(matches!(
+            ledmatrix.write_frame_resilient(17, &[1u8, 2], 3),
+            Err(Error::DotOutOfRange)
+        ));
+
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_degrade_and_recover() {
+        let configuration = Configuration::new::<Variant1>();
+        let policy = DegradationPolicy {
+            error_threshold: 2,
+            recovery_interval: 2,
+        };
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::FailWrite(0x200),
+            Access::FailWrite(0x200),
+            // first write while degraded is skipped, second one attempts recovery
+            Access::ReadRegisters(0x000, vec![0; 5]),
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x001, configuration.register_values().to_vec()),
+            Access::WriteRegister(0x200, 5),
+        ]);
+
+        let ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let mut supervised = Supervised::new(ledmatrix, configuration, policy);
+
+        assert_eq!(supervised.set_pwm(0, &[1u8]).unwrap(), BusState::Healthy);
+        assert_eq!(supervised.set_pwm(0, &[2u8]).unwrap(), BusState::Degraded);
+        assert_eq!(supervised.set_pwm(0, &[3u8]).unwrap(), BusState::Degraded);
+        assert_eq!(supervised.set_pwm(0, &[5u8]).unwrap(), BusState::Healthy);
+
+        supervised.into_inner().release().done();
+    }
+
+    #[test]
+    fn test_out_of_range_does_not_degrade() {
+        let configuration = Configuration::new::<Variant1>();
+        let policy = DegradationPolicy {
+            error_threshold: 2,
+            recovery_interval: 2,
+        };
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegister(0x200, 5),
+        ]);
+
+        let ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let mut supervised = Supervised::new(ledmatrix, configuration, policy);
+
+        for _ in 0..3 {
+            assert!(matches!(
+                supervised.set_pwm(18, &[1u8]),
+                Err(Error::DotOutOfRange)
+            ));
+        }
+        assert_eq!(supervised.state(), BusState::Healthy);
+        assert_eq!(supervised.set_pwm(0, &[5u8]).unwrap(), BusState::Healthy);
+
+        supervised.into_inner().release().done();
+    }
+}
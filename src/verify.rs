@@ -0,0 +1,149 @@
+//! Sampled read back of written PWM values, as an early warning for marginal wiring.
+
+use crate::{interface::RegisterAccess, DeviceVariant, Dot, Error, Lp586x, PwmAccess};
+
+/// Bus health monitor reading back a few PWM values every `interval` flushes.
+///
+/// Call [`WriteVerifier::after_flush`] after every frame write with the frame just
+/// written (the shadow frame, e.g. [`crate::framebuffer::Framebuffer::values`]). Every
+/// `interval` calls `samples` pseudo-randomly chosen dots are read back and compared,
+/// so over time all dots get checked at a small bus cost per frame.
+pub struct WriteVerifier<DV> {
+    interval: u16,
+    samples: u8,
+    flushes: u16,
+    random: u32,
+    checked: u32,
+    mismatches: u32,
+    _phantom_data: core::marker::PhantomData<DV>,
+}
+
+impl<DV: DeviceVariant> WriteVerifier<DV> {
+    /// Create a verifier reading back `samples` dots every `interval` flushes.
+    pub fn new(interval: u16, samples: u8) -> Self {
+        Self {
+            interval: interval.max(1),
+            samples,
+            flushes: 0,
+            random: 0x2545_f491,
+            checked: 0,
+            mismatches: 0,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    /// Seeds the choice of sampled dots, e.g. with a device serial number so not all
+    /// devices of an installation sample the same dots.
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.random = seed.max(1);
+        self
+    }
+
+    /// Number of dots read back so far.
+    pub fn checked(&self) -> u32 {
+        self.checked
+    }
+
+    /// Number of dots read back with a value differing from the shadow frame.
+    pub fn mismatches(&self) -> u32 {
+        self.mismatches
+    }
+
+    /// Clears the counters, e.g. after reporting them as telemetry.
+    pub fn reset_counters(&mut self) {
+        self.checked = 0;
+        self.mismatches = 0;
+    }
+
+    fn next_dot(&mut self) -> u16 {
+        // xorshift32
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 17;
+        self.random ^= self.random << 5;
+
+        (self.random % DV::NUM_DOTS as u32) as u16
+    }
+
+    /// Counts a flush of `shadow`, holding the PWM values written starting at L0-CS0.
+    /// Every `interval` flushes, reads back the sampled dots covered by `shadow` and
+    /// calls `on_mismatch` with the dot, the written and the read value for every
+    /// mismatch.
+    pub fn after_flush<I, DM, IE, T>(
+        &mut self,
+        driver: &mut Lp586x<DV, I, DM>,
+        shadow: &[T],
+        mut on_mismatch: impl FnMut(Dot<DV>, T, T),
+    ) -> Result<(), Error<IE>>
+    where
+        T: Copy + PartialEq,
+        I: RegisterAccess<Error = Error<IE>>,
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.flushes += 1;
+        if self.flushes < self.interval {
+            return Ok(());
+        }
+        self.flushes = 0;
+
+        for _ in 0..self.samples {
+            let dot = self.next_dot();
+            let Some(&written) = shadow.get(dot as usize) else {
+                continue;
+            };
+
+            let read = driver.get_pwm(dot)?;
+            self.checked = self.checked.saturating_add(1);
+            if read != written {
+                self.mismatches = self.mismatches.saturating_add(1);
+                on_mismatch(Dot::with_index(dot), written, read);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface},
+        Lp5861, Variant1,
+    };
+
+    #[test]
+    fn test_write_verifier() {
+        let mut verifier = WriteVerifier::<Variant1>::new(2, 2).with_seed(7);
+        let dots = {
+            let mut sampler = WriteVerifier::<Variant1>::new(2, 2).with_seed(7);
+            [sampler.next_dot(), sampler.next_dot()]
+        };
+        let shadow: Vec<u8> = (0..18).collect();
+
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::ReadRegister(0x200 + dots[0], dots[0] as u8),
+            Access::ReadRegister(0x200 + dots[1], 0xee),
+        ]);
+
+        let mut ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+
+        let mut mismatches = Vec::new();
+        for _ in 0..2 {
+            verifier
+                .after_flush(&mut ledmatrix, &shadow, |dot, written, read| {
+                    mismatches.push((dot.index(), written, read))
+                })
+                .unwrap();
+        }
+
+        assert_eq!(mismatches, vec![(dots[1], dots[1] as u8, 0xee)]);
+        assert_eq!((verifier.checked(), verifier.mismatches()), (2, 1));
+
+        ledmatrix.release().done();
+    }
+}
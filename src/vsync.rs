@@ -0,0 +1,169 @@
+//! Driving the VSYNC input from the driver, for Mode 2 and Mode 3.
+
+use embedded_hal::{blocking::delay::DelayUs, digital::v2::OutputPin};
+
+use crate::{interface::RegisterAccess, DeviceVariant, Error, Lp586x, PwmAccess};
+
+/// Minimum VSYNC high time issued when latching a frame.
+pub const VSYNC_PULSE_US: u32 = 1;
+
+/// Error of a [`VsyncDriver`] operation.
+#[derive(Debug)]
+pub enum VsyncError<IE, PE> {
+    /// Driver error
+    Driver(Error<IE>),
+    /// Error setting the VSYNC pin
+    Pin(PE),
+}
+
+/// Pin connected to VSYNC, with the delay timing its pulses.
+///
+/// Standalone helper for Mode 2 and Mode 3 setups writing the PWM values themselves,
+/// e.g. through a [`crate::compose::Compositor`]. With a delay, every pulse stays high
+/// for at least [`VSYNC_PULSE_US`]. Without one, the pulse is as wide as the time
+/// between two pin writes, which is only sufficient on MCUs with slow GPIO access.
+pub struct Vsync<P, D> {
+    pin: P,
+    delay: Option<D>,
+}
+
+impl<P: OutputPin, D: DelayUs<u32>> Vsync<P, D> {
+    /// Takes ownership of the `pin`, which is driven low, and the optional `delay`.
+    pub fn new(mut pin: P, delay: Option<D>) -> Result<Self, P::Error> {
+        pin.set_low()?;
+
+        Ok(Self { pin, delay })
+    }
+
+    /// Issues a single VSYNC pulse, latching all PWM values written since the last one.
+    pub fn pulse(&mut self) -> Result<(), P::Error> {
+        pulse_pin(&mut self.pin, self.delay.as_mut())
+    }
+
+    /// Destroys the helper and returns the pin and the delay.
+    pub fn release(self) -> (P, Option<D>) {
+        (self.pin, self.delay)
+    }
+}
+
+/// Drives `pin` high for at least [`VSYNC_PULSE_US`], if a `delay` is given.
+pub(crate) fn pulse_pin<P: OutputPin, D: DelayUs<u32>>(
+    pin: &mut P,
+    delay: Option<&mut D>,
+) -> Result<(), P::Error> {
+    pin.set_high()?;
+    if let Some(delay) = delay {
+        delay.delay_us(VSYNC_PULSE_US);
+    }
+    pin.set_low()
+}
+
+/// Driver owning the [`Vsync`] pin and delay.
+///
+/// In [`crate::DataRefMode::Mode2`] and [`crate::DataRefMode::Mode3`] written PWM
+/// values take effect on the next VSYNC pulse. [`VsyncDriver::flush`] writes the
+/// values and issues the pulse, so single controller setups don't need to manage the
+/// pin themselves.
+pub struct VsyncDriver<DV, I, DM, P, D> {
+    driver: Lp586x<DV, I, DM>,
+    vsync: Vsync<P, D>,
+}
+
+impl<DV: DeviceVariant, I, DM, P, D, IE> VsyncDriver<DV, I, DM, P, D>
+where
+    I: RegisterAccess<Error = Error<IE>>,
+    P: OutputPin,
+    D: DelayUs<u32>,
+{
+    /// Takes ownership of `driver` and `vsync`.
+    pub fn new(driver: Lp586x<DV, I, DM>, vsync: Vsync<P, D>) -> Self {
+        Self { driver, vsync }
+    }
+
+    /// Access the driver, e.g. for writing PWM values without a VSYNC pulse.
+    pub fn driver(&mut self) -> &mut Lp586x<DV, I, DM> {
+        &mut self.driver
+    }
+
+    /// Destroys the wrapper and returns the driver and the VSYNC helper.
+    pub fn into_inner(self) -> (Lp586x<DV, I, DM>, Vsync<P, D>) {
+        (self.driver, self.vsync)
+    }
+
+    /// Issues a single VSYNC pulse, latching all PWM values written since the last one.
+    pub fn pulse(&mut self) -> Result<(), VsyncError<IE, P::Error>> {
+        self.vsync.pulse().map_err(VsyncError::Pin)
+    }
+
+    /// Writes PWM `values` starting at dot `start` and issues a VSYNC pulse.
+    pub fn flush<T>(&mut self, start: u16, values: &[T]) -> Result<(), VsyncError<IE, P::Error>>
+    where
+        Lp586x<DV, I, DM>: PwmAccess<T, Error = Error<IE>>,
+    {
+        self.driver
+            .set_pwm(start, values)
+            .map_err(VsyncError::Driver)?;
+
+        self.pulse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::mock::{Access, MockInterface, NoDelay},
+        Lp5861,
+    };
+
+    #[derive(Default)]
+    struct FakePin {
+        levels: Vec<bool>,
+    }
+
+    impl OutputPin for FakePin {
+        type Error = ();
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.levels.push(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.levels.push(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flush_with_vsync() {
+        let interface = MockInterface::new(vec![
+            Access::WriteRegister(0x0a9, 0xff),
+            Access::WriteRegister(0x000, 1),
+            Access::WriteRegisters(0x200, vec![1, 2]),
+        ]);
+
+        let ledmatrix = Lp5861::new(interface)
+            .unwrap()
+            .into_8bit_data_mode()
+            .unwrap();
+        let vsync = Vsync::new(FakePin::default(), Some(NoDelay)).unwrap();
+        let mut vsync_driver = VsyncDriver::new(ledmatrix, vsync);
+        vsync_driver.flush(0, &[1u8, 2]).unwrap();
+
+        let (ledmatrix, vsync) = vsync_driver.into_inner();
+        let (pin, _) = vsync.release();
+        assert_eq!(pin.levels, vec![false, true, false]);
+        ledmatrix.release().done();
+    }
+
+    #[test]
+    fn test_vsync() {
+        let mut vsync = Vsync::new(FakePin::default(), Some(NoDelay)).unwrap();
+        vsync.pulse().unwrap();
+        vsync.pulse().unwrap();
+
+        let (pin, _) = vsync.release();
+        assert_eq!(pin.levels, vec![false, true, false, true, false]);
+    }
+}